@@ -0,0 +1,178 @@
+//! Derive macros for [`copyspan`](https://docs.rs/copyspan). Use these through
+//! the `derive` feature of `copyspan` instead of depending on this crate
+//! directly.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, parse_macro_input, parse_quote};
+
+/// Implements `SpanIndex`, `Add` and `Sub` for a struct with a single field
+/// by forwarding to that field.
+///
+/// The struct also needs to implement `Clone`, `Copy`, `Debug`, `Default`,
+/// `Eq`, `Ord` and `Hash`. Adding `#[span_index(bytes)]` also implements
+/// `ByteIndex`, which allows spans of the struct to index strings.
+/// ```rust
+/// use copyspan::{Span, SpanIndex};
+///
+/// #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, SpanIndex)]
+/// #[span_index(bytes)]
+/// struct ByteOffset(u32);
+///
+/// #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, SpanIndex)]
+/// struct TokenIdx {
+///     index: usize,
+/// }
+///
+/// let span = Span::from(ByteOffset(6)..ByteOffset(11));
+/// assert_eq!(&"hello world"[span], "world");
+///
+/// let tokens = Span::from(TokenIdx { index: 2 }..TokenIdx { index: 5 });
+/// assert_eq!(tokens.len(), TokenIdx { index: 3 });
+/// ```
+#[proc_macro_derive(SpanIndex, attributes(span_index))]
+pub fn derive_span_index(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    span_index(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn span_index(mut input: DeriveInput) -> Result<TokenStream, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`SpanIndex` can only be derived for structs",
+        ));
+    };
+
+    let (field, inner) = match &data.fields {
+        Fields::Named(fields) if fields.named.len() == 1 => {
+            let field = &fields.named[0];
+            let ident = &field.ident;
+            (quote!(#ident), &field.ty)
+        }
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => (quote!(0), &fields.unnamed[0].ty),
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`SpanIndex` can only be derived for structs with exactly one field",
+            ));
+        }
+    };
+
+    let mut bytes = false;
+
+    for attr in &input.attrs {
+        if attr.path().is_ident("span_index") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("bytes") {
+                    bytes = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `bytes`"))
+                }
+            })?;
+        }
+    }
+
+    let inner = inner.clone();
+    let name = &input.ident;
+
+    input
+        .generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#inner: ::copyspan::SpanIndex));
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let wrap = |value: TokenStream| quote!(Self { #field: #value });
+    let forward = quote!(<#inner as ::copyspan::SpanIndex>);
+
+    let zero = wrap(quote!(#forward::ZERO));
+    let one = wrap(quote!(#forward::ONE));
+    let min = wrap(quote!(#forward::MIN));
+    let max = wrap(quote!(#forward::MAX));
+    let from_value = wrap(quote!(value));
+    let add = wrap(quote!(self.#field + rhs.#field));
+    let sub = wrap(quote!(self.#field - rhs.#field));
+
+    let checked = ["checked_add", "checked_sub"].map(|op| {
+        let op = syn::Ident::new(op, proc_macro2::Span::call_site());
+        quote! {
+            fn #op(self, rhs: Self) -> ::core::option::Option<Self> {
+                #forward::#op(self.#field, rhs.#field).map(|value| #from_value)
+            }
+        }
+    });
+
+    let unchecked = [
+        "saturating_add",
+        "saturating_sub",
+        "wrapping_add",
+        "wrapping_sub",
+    ]
+    .map(|op| {
+        let op = syn::Ident::new(op, proc_macro2::Span::call_site());
+        let value = wrap(quote!(#forward::#op(self.#field, rhs.#field)));
+        quote! {
+            fn #op(self, rhs: Self) -> Self {
+                #value
+            }
+        }
+    });
+
+    let byte_index = bytes.then(|| {
+        quote! {
+            impl #impl_generics ::copyspan::ByteIndex for #name #ty_generics #where_clause {}
+        }
+    });
+
+    Ok(quote! {
+        #byte_index
+
+        impl #impl_generics ::copyspan::SpanIndex for #name #ty_generics #where_clause {
+            const ZERO: Self = #zero;
+            const ONE: Self = #one;
+            const MIN: Self = #min;
+            const MAX: Self = #max;
+
+            fn to_i128(self) -> i128 {
+                #forward::to_i128(self.#field)
+            }
+
+            fn from_i128(value: i128) -> ::core::option::Option<Self> {
+                #forward::from_i128(value).map(|value| #from_value)
+            }
+
+            fn to_usize(self) -> ::core::option::Option<usize> {
+                #forward::to_usize(self.#field)
+            }
+
+            fn from_usize(value: usize) -> ::core::option::Option<Self> {
+                #forward::from_usize(value).map(|value| #from_value)
+            }
+
+            #(#checked)*
+            #(#unchecked)*
+        }
+
+        impl #impl_generics ::core::ops::Add for #name #ty_generics #where_clause {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                #add
+            }
+        }
+
+        impl #impl_generics ::core::ops::Sub for #name #ty_generics #where_clause {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                #sub
+            }
+        }
+    })
+}
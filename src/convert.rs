@@ -0,0 +1,140 @@
+use core::ops::{Bound, RangeBounds, RangeInclusive};
+
+use crate::{Span, SpanError, SpanIndex};
+
+/// Implements `From` between spans wherever the integer types implement `From`
+macro_rules! impl_widening {
+    ($from:ty => $($to:ty),*) => {$(
+        impl From<Span<$from>> for Span<$to> {
+            fn from(value: Span<$from>) -> Self {
+                Span {
+                    start: value.start.into(),
+                    end: value.end.into(),
+                }
+            }
+        }
+    )*};
+}
+
+/// Implements `TryFrom` between spans wherever the integer types only implement
+/// `TryFrom`
+macro_rules! impl_narrowing {
+    ($from:ty => $($to:ty),*) => {$(
+        impl TryFrom<Span<$from>> for Span<$to> {
+            type Error = SpanError;
+
+            fn try_from(value: Span<$from>) -> Result<Self, Self::Error> {
+                value.try_cast()
+            }
+        }
+    )*};
+}
+
+impl_widening!(u8 => u16, u32, u64, usize, i16, i32, i64, isize);
+impl_widening!(u16 => u32, u64, usize, i32, i64);
+impl_widening!(u32 => u64, i64);
+impl_widening!(i8 => i16, i32, i64, isize);
+impl_widening!(i16 => i32, i64, isize);
+impl_widening!(i32 => i64);
+
+impl_narrowing!(u8 => i8);
+impl_narrowing!(u16 => u8, i8, i16, isize);
+impl_narrowing!(u32 => u8, u16, usize, i8, i16, i32, isize);
+impl_narrowing!(u64 => u8, u16, u32, usize, i8, i16, i32, i64, isize);
+impl_narrowing!(usize => u8, u16, u32, u64, i8, i16, i32, i64, isize);
+impl_narrowing!(i8 => u8, u16, u32, u64, usize);
+impl_narrowing!(i16 => u8, u16, u32, u64, usize, i8);
+impl_narrowing!(i32 => u8, u16, u32, u64, usize, i8, i16, isize);
+impl_narrowing!(i64 => u8, u16, u32, u64, usize, i8, i16, i32, isize);
+impl_narrowing!(isize => u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+/// Fails if the range's end is the largest value of `T`
+impl<T: SpanIndex> TryFrom<RangeInclusive<T>> for Span<T> {
+    type Error = SpanError;
+
+    fn try_from(value: RangeInclusive<T>) -> Result<Self, Self::Error> {
+        let (start, end) = value.into_inner();
+        let end = end.checked_add(T::ONE).ok_or(SpanError::Overflow)?;
+
+        Span::new(start, end)
+    }
+}
+
+/// Fails if the span is empty and starts at the smallest value of `T`
+impl<T: SpanIndex> TryFrom<Span<T>> for RangeInclusive<T> {
+    type Error = SpanError;
+
+    fn try_from(value: Span<T>) -> Result<Self, Self::Error> {
+        let end = value.end.checked_sub(T::ONE).ok_or(SpanError::Overflow)?;
+
+        Ok(value.start..=end)
+    }
+}
+
+/// Fails if either bound is [`Bound::Unbounded`]. Use [`Span::from_bounds`]
+/// to resolve unbounded ranges against a length.
+impl<T: SpanIndex> TryFrom<(Bound<T>, Bound<T>)> for Span<T> {
+    type Error = SpanError;
+
+    fn try_from((start, end): (Bound<T>, Bound<T>)) -> Result<Self, Self::Error> {
+        let start = match start {
+            Bound::Included(start) => start,
+            Bound::Excluded(start) => start.checked_add(T::ONE).ok_or(SpanError::Overflow)?,
+            Bound::Unbounded => return Err(SpanError::Unbounded),
+        };
+
+        let end = match end {
+            Bound::Included(end) => end.checked_add(T::ONE).ok_or(SpanError::Overflow)?,
+            Bound::Excluded(end) => end,
+            Bound::Unbounded => return Err(SpanError::Unbounded),
+        };
+
+        Span::new(start, end)
+    }
+}
+
+impl<T: SpanIndex> From<Span<T>> for (Bound<T>, Bound<T>) {
+    fn from(value: Span<T>) -> Self {
+        (Bound::Included(value.start), Bound::Excluded(value.end))
+    }
+}
+
+/// Allows spans to be passed anywhere that the standard library takes a range
+/// ```rust
+/// # use copyspan::Span;
+/// # use std::collections::BTreeMap;
+/// let span = Span::from(1..3);
+///
+/// let mut text = String::from("hello");
+/// text.replace_range(span, "ipp");
+/// assert_eq!(text, "hipplo");
+///
+/// let mut bytes = *b"abcdef";
+/// bytes.copy_within(span, 3);
+/// assert_eq!(&bytes, b"abcbcf");
+///
+/// let map = BTreeMap::from([(0, 'a'), (2, 'b'), (4, 'c')]);
+/// assert_eq!(map.range(span).collect::<Vec<_>>(), [(&2, &'b')]);
+///
+/// let mut vec = vec![1, 2, 3, 4];
+/// assert_eq!(vec.drain(&span).collect::<Vec<_>>(), [2, 3]);
+/// ```
+impl<T: SpanIndex> RangeBounds<T> for Span<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        Bound::Included(&self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        Bound::Excluded(&self.end)
+    }
+}
+
+impl<T: SpanIndex> RangeBounds<T> for &Span<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        Bound::Included(&self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        Bound::Excluded(&self.end)
+    }
+}
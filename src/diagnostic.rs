@@ -0,0 +1,585 @@
+//! Compiler-style rendering of messages that point at [`Span`]s in source text.
+//!
+//! ```
+//! use copyspan::Span;
+//! use copyspan::diagnostic::{ColorMode, Diagnostic, Label, Severity};
+//!
+//! let src = "let x: u32 = \"hi\";\n";
+//! let diagnostic = Diagnostic::new(Severity::Error, "mismatched types")
+//!     .with_origin("main.rs")
+//!     .with_label(Label::primary(Span::from(13..17), "expected `u32`, found `&str`"))
+//!     .with_label(Label::secondary(Span::from(7..10), "expected due to this"))
+//!     .with_note("strings can't be implicitly converted to integers");
+//!
+//! let expected = "\
+//! error: mismatched types
+//!  --> main.rs:1:14
+//!   |
+//! 1 | let x: u32 = \"hi\";
+//!   |        ---   ^^^^ expected `u32`, found `&str`
+//!   |        |
+//!   |        expected due to this
+//!   |
+//!   = note: strings can't be implicitly converted to integers
+//! ";
+//!
+//! assert_eq!(diagnostic.render(src, ColorMode::Plain).unwrap(), expected);
+//! ```
+
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display, Write};
+
+use crate::{
+    LineIndex, Span,
+    line_index::{ColumnUnit, LineIndexError},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl Severity {
+    const fn color(self) -> &'static str {
+        match self {
+            Severity::Error => "\x1b[1;31m",
+            Severity::Warning => "\x1b[1;33m",
+            Severity::Note => "\x1b[1;32m",
+            Severity::Help => "\x1b[1;36m",
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        })
+    }
+}
+
+/// Whether rendered output contains ANSI color codes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorMode {
+    #[default]
+    Plain,
+    Ansi,
+}
+
+/// A span of source text with a message attached.
+///
+/// Primary labels are underlined with `^` and secondary labels with `-`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Label {
+    pub span: Span<usize>,
+    pub message: String,
+    pub primary: bool,
+}
+
+impl Label {
+    #[must_use]
+    pub fn primary(span: Span<usize>, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+            primary: true,
+        }
+    }
+
+    #[must_use]
+    pub fn secondary(span: Span<usize>, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+            primary: false,
+        }
+    }
+}
+
+/// A message about some source text, built up with the `with_*` methods and
+/// turned into text with [`Diagnostic::render`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// The name of the file shown next to the location of the first primary
+    /// label
+    pub origin: Option<String>,
+    pub labels: Vec<Label>,
+    /// Extra messages shown below the source text
+    pub notes: Vec<(Severity, String)>,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            origin: None,
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_origin(mut self, origin: impl Into<String>) -> Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    #[must_use]
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push((Severity::Note, note.into()));
+        self
+    }
+
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.notes.push((Severity::Help, help.into()));
+        self
+    }
+
+    /// Renders this diagnostic against the text that its labels point into.
+    ///
+    /// Fails if a label is out of bounds for `src` or does not lie on char
+    /// boundaries.
+    ///
+    /// Labels that span several lines are drawn in the margin, and underlines
+    /// pass behind the margin lines of labels that are still open.
+    /// ```rust
+    /// # use copyspan::Span;
+    /// # use copyspan::diagnostic::{ColorMode, Diagnostic, Label, Severity};
+    /// let src = "fn a() {\n    b();\n}\nc();\n";
+    /// let diagnostic = Diagnostic::new(Severity::Error, "overlapping labels")
+    ///     .with_label(Label::primary(Span::from(0..20), "first"))
+    ///     .with_label(Label::secondary(Span::from(13..24), "second"));
+    ///
+    /// let expected = "\
+    /// error: overlapping labels
+    ///   |
+    /// 1 |     fn a() {
+    ///   |  ___^
+    /// 2 | |       b();
+    ///   | |  _____-
+    /// 3 | | | }
+    ///   | |_|__^ first
+    /// 4 |   | c();
+    ///   |   |____- second
+    /// ";
+    ///
+    /// assert_eq!(diagnostic.render(src, ColorMode::Plain).unwrap(), expected);
+    /// ```
+    pub fn render(&self, src: &str, color: ColorMode) -> Result<String, LineIndexError> {
+        let lines = LineIndex::new(src);
+        let labels = self
+            .labels
+            .iter()
+            .map(|label| LaidOutLabel::new(label, src, &lines))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut out = Out {
+            buf: String::new(),
+            color,
+            severity: self.severity,
+        };
+
+        out.paint(Paint::Severity(self.severity), &self.severity.to_string());
+        out.paint(Paint::Bold, &alloc::format!(": {}", self.message));
+        out.buf.push('\n');
+
+        let shown_lines: BTreeSet<usize> =
+            labels.iter().flat_map(LaidOutLabel::shown_lines).collect();
+        let gutter = shown_lines.last().map_or(0, |&l| (l + 1).to_string().len());
+
+        if let Some(origin) = &self.origin {
+            let location = labels.iter().find(|l| l.primary).or(labels.first());
+
+            out.buf.extend(core::iter::repeat_n(' ', gutter));
+            out.paint(Paint::Gutter, "--> ");
+            out.buf.push_str(origin);
+            if let Some(l) = location {
+                let col = lines.line_col(l.span.start, ColumnUnit::Char)?.col;
+                let _ = write!(out.buf, ":{}:{}", l.start_line + 1, col + 1);
+            }
+            out.buf.push('\n');
+        }
+
+        if !labels.is_empty() {
+            out.gutter_row(gutter);
+            Self::render_lines(&mut out, src, &lines, &labels, &shown_lines, gutter);
+        }
+
+        if !self.notes.is_empty() {
+            out.gutter_row(gutter);
+        }
+
+        for (severity, note) in &self.notes {
+            out.buf.extend(core::iter::repeat_n(' ', gutter + 1));
+            out.paint(Paint::Gutter, "= ");
+            out.paint(Paint::Bold, &alloc::format!("{severity}:"));
+            out.buf.push(' ');
+            out.buf.push_str(note);
+            out.buf.push('\n');
+        }
+
+        Ok(out.buf)
+    }
+
+    fn render_lines(
+        out: &mut Out,
+        src: &str,
+        lines: &LineIndex,
+        labels: &[LaidOutLabel],
+        shown_lines: &BTreeSet<usize>,
+        gutter: usize,
+    ) {
+        let multiline: Vec<&LaidOutLabel> = labels.iter().filter(|l| l.is_multiline()).collect();
+        let margin = multiline.len() * 2;
+        let mut active = alloc::vec![false; multiline.len()];
+        let mut prev_line = None;
+
+        for &line in shown_lines {
+            if prev_line.is_some_and(|prev| prev + 1 != line) {
+                out.buf.push_str("...\n");
+            }
+            prev_line = Some(line);
+
+            let text = line_text(src, lines, line);
+
+            let mut row = Row::default();
+            for (slot, _) in active.iter().enumerate().filter(|(_, a)| **a) {
+                row.put(slot * 2, '|', multiline[slot].paint());
+            }
+            row.put_str(margin, &expand_tabs(text), Paint::None);
+            out.source_row(gutter, line, &row);
+
+            let mut single: Vec<&LaidOutLabel> = labels
+                .iter()
+                .filter(|l| !l.is_multiline() && l.start_line == line)
+                .collect();
+            single.sort_by_key(|l| (l.start_col, l.end_col));
+
+            let base = Row::margin(&active, &multiline);
+            for row in single_line_rows(&single, margin, &base) {
+                out.annotation_row(gutter, &row);
+            }
+
+            for (slot, label) in multiline.iter().enumerate() {
+                if label.start_line != line {
+                    continue;
+                }
+
+                let mut row = Row::margin(&active, &multiline);
+                for col in slot * 2 + 1..margin + label.start_col {
+                    row.put_blank(col, '_', label.paint());
+                }
+                row.put(margin + label.start_col, label.marker(), label.paint());
+                out.annotation_row(gutter, &row);
+
+                active[slot] = true;
+            }
+
+            for (slot, label) in multiline.iter().enumerate() {
+                if label.end_line != line {
+                    continue;
+                }
+
+                let mut row = Row::margin(&active, &multiline);
+                let marker_col = margin + label.end_col.saturating_sub(1);
+                for col in slot * 2 + 1..marker_col {
+                    row.put_blank(col, '_', label.paint());
+                }
+                row.put(marker_col, label.marker(), label.paint());
+                if !label.message.is_empty() {
+                    row.put_str(marker_col + 2, label.message, label.paint());
+                }
+                out.annotation_row(gutter, &row);
+
+                active[slot] = false;
+            }
+        }
+    }
+}
+
+/// A label with its position converted to lines and display columns
+struct LaidOutLabel<'a> {
+    span: Span<usize>,
+    message: &'a str,
+    primary: bool,
+    start_line: usize,
+    start_col: usize,
+    end_line: usize,
+    /// The display column after the last marker. This is always greater than
+    /// `start_col` for single-line labels so that zero-width spans still get a
+    /// marker.
+    end_col: usize,
+}
+
+impl<'a> LaidOutLabel<'a> {
+    fn new(label: &'a Label, src: &str, lines: &LineIndex) -> Result<Self, LineIndexError> {
+        let span = label.span;
+        let (start, mut end) = lines.span_line_cols(span, ColumnUnit::Utf8)?;
+
+        // A span ending right after a newline is drawn as ending at that newline
+        if !span.is_empty() && end.col == 0 && end.line > start.line {
+            end = lines.line_col(span.end - 1, ColumnUnit::Utf8)?;
+            end.col += 1;
+        }
+
+        let start_col = display_col(line_text(src, lines, start.line), start.col);
+        let mut end_col = display_col(line_text(src, lines, end.line), end.col);
+
+        if start.line == end.line {
+            end_col = end_col.max(start_col + 1);
+        }
+
+        Ok(Self {
+            span,
+            message: &label.message,
+            primary: label.primary,
+            start_line: start.line,
+            start_col,
+            end_line: end.line,
+            end_col,
+        })
+    }
+
+    fn is_multiline(&self) -> bool {
+        self.start_line != self.end_line
+    }
+
+    /// The lines that are printed for this label. Long multi-line labels only
+    /// show their first and last two lines.
+    fn shown_lines(&self) -> impl Iterator<Item = usize> {
+        let (start, end) = (self.start_line, self.end_line);
+
+        (start..=end).filter(move |&l| l <= start + 1 || l + 1 >= end)
+    }
+
+    fn marker(&self) -> char {
+        if self.primary { '^' } else { '-' }
+    }
+
+    fn paint(&self) -> Paint {
+        if self.primary {
+            Paint::Primary
+        } else {
+            Paint::Secondary
+        }
+    }
+}
+
+/// Lays out the labels that start and end on one line. The last label's
+/// message goes right after the underlines, and every other message gets its
+/// own row with a line connecting it to its underline.
+fn single_line_rows(labels: &[&LaidOutLabel], margin: usize, base: &Row) -> Vec<Row> {
+    if labels.is_empty() {
+        return Vec::new();
+    }
+
+    let mut underline = base.clone();
+    for label in labels
+        .iter()
+        .filter(|l| !l.primary)
+        .chain(labels.iter().filter(|l| l.primary))
+    {
+        for col in label.start_col..label.end_col {
+            underline.put(margin + col, label.marker(), label.paint());
+        }
+    }
+
+    let (last, rest) = labels.split_last().unwrap();
+    if !last.message.is_empty() {
+        let end = labels.iter().map(|l| l.end_col).max().unwrap_or(0);
+        underline.put_str(margin + end + 1, last.message, last.paint());
+    }
+
+    let mut rows = Vec::from([underline]);
+    let mut pending: Vec<&LaidOutLabel> = rest
+        .iter()
+        .copied()
+        .filter(|l| !l.message.is_empty())
+        .collect();
+
+    while let Some(label) = pending.pop() {
+        let mut connector = base.clone();
+        for l in pending.iter().chain([&label]) {
+            connector.put(margin + l.start_col, '|', l.paint());
+        }
+
+        let mut message = base.clone();
+        for l in &pending {
+            message.put(margin + l.start_col, '|', l.paint());
+        }
+        message.put_str(margin + label.start_col, label.message, label.paint());
+
+        rows.push(connector);
+        rows.push(message);
+    }
+
+    rows
+}
+
+/// The text of a line without its line terminator
+fn line_text<'s>(src: &'s str, lines: &LineIndex, line: usize) -> &'s str {
+    let span = lines.line_span(line).unwrap_or_default();
+    let text = &src[span];
+
+    text.strip_suffix('\n')
+        .map(|t| t.strip_suffix('\r').unwrap_or(t))
+        .unwrap_or(text)
+}
+
+const TAB_WIDTH: usize = 4;
+
+/// The column that a byte offset in `text` is drawn at once tabs are expanded
+fn display_col(text: &str, byte_col: usize) -> usize {
+    let prefix = &text[..byte_col.min(text.len())];
+    let width: usize = prefix
+        .chars()
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum();
+
+    // Columns past the line's text (such as its newline) are drawn one cell
+    // apart
+    width + byte_col.saturating_sub(text.len())
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', &" ".repeat(TAB_WIDTH))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Paint {
+    None,
+    Bold,
+    Gutter,
+    Primary,
+    Secondary,
+    Severity(Severity),
+}
+
+/// A row of annotation characters that each have their own color
+#[derive(Clone, Default)]
+struct Row {
+    cells: Vec<(char, Paint)>,
+}
+
+impl Row {
+    /// A row with the margin lines of every active multi-line label
+    fn margin(active: &[bool], multiline: &[&LaidOutLabel]) -> Self {
+        let mut row = Self::default();
+        for (slot, _) in active.iter().enumerate().filter(|(_, a)| **a) {
+            row.put(slot * 2, '|', multiline[slot].paint());
+        }
+
+        row
+    }
+
+    fn put(&mut self, col: usize, c: char, paint: Paint) {
+        if self.cells.len() <= col {
+            self.cells.resize(col + 1, (' ', Paint::None));
+        }
+
+        self.cells[col] = (c, paint);
+    }
+
+    /// Puts a character only where nothing has been drawn yet, so that
+    /// underlines pass behind the margin lines of other labels
+    fn put_blank(&mut self, col: usize, c: char, paint: Paint) {
+        if self.cells.get(col).is_none_or(|&(cell, _)| cell == ' ') {
+            self.put(col, c, paint);
+        }
+    }
+
+    fn put_str(&mut self, col: usize, s: &str, paint: Paint) {
+        for (i, c) in s.chars().enumerate() {
+            self.put(col + i, c, paint);
+        }
+    }
+}
+
+struct Out {
+    buf: String,
+    color: ColorMode,
+    severity: Severity,
+}
+
+impl Out {
+    fn paint(&mut self, paint: Paint, s: &str) {
+        let code = match (self.color, paint) {
+            (ColorMode::Plain, _) | (_, Paint::None) => None,
+            (_, Paint::Bold) => Some("\x1b[1m"),
+            (_, Paint::Gutter | Paint::Secondary) => Some("\x1b[1;34m"),
+            (_, Paint::Primary) => Some(self.severity.color()),
+            (_, Paint::Severity(severity)) => Some(severity.color()),
+        };
+
+        match code {
+            Some(code) => {
+                let _ = write!(self.buf, "{code}{s}\x1b[0m");
+            }
+            None => self.buf.push_str(s),
+        }
+    }
+
+    fn gutter_row(&mut self, gutter: usize) {
+        self.buf.extend(core::iter::repeat_n(' ', gutter));
+        self.paint(Paint::Gutter, " |");
+        self.buf.push('\n');
+    }
+
+    fn source_row(&mut self, gutter: usize, line: usize, row: &Row) {
+        self.paint(Paint::Gutter, &alloc::format!("{:>gutter$} |", line + 1));
+        self.row(row);
+    }
+
+    fn annotation_row(&mut self, gutter: usize, row: &Row) {
+        self.buf.extend(core::iter::repeat_n(' ', gutter));
+        self.paint(Paint::Gutter, " |");
+        self.row(row);
+    }
+
+    fn row(&mut self, row: &Row) {
+        let end = row
+            .cells
+            .iter()
+            .rposition(|(c, _)| *c != ' ')
+            .map_or(0, |i| i + 1);
+        let mut cells = &row.cells[..end];
+
+        if !cells.is_empty() {
+            self.buf.push(' ');
+        }
+
+        while let Some(&(_, paint)) = cells.first() {
+            let len = cells
+                .iter()
+                .position(|(_, p)| *p != paint)
+                .unwrap_or(cells.len());
+            let text: String = cells[..len].iter().map(|(c, _)| c).collect();
+
+            self.paint(paint, &text);
+            cells = &cells[len..];
+        }
+
+        self.buf.push('\n');
+    }
+}
@@ -0,0 +1,240 @@
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, vec::Vec};
+use core::{fmt::Debug, slice};
+
+use crate::{FromBytesError, Span, SpanIndex};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An integer that has the same size on every target, which is any primitive
+/// integer other than `usize` and `isize`.
+///
+/// # Safety
+/// Every bit pattern must be a valid value, and the type must have no padding
+/// or interior mutability.
+#[doc(hidden)]
+pub unsafe trait FixedInt: SpanIndex + sealed::Sealed {
+    fn to_le(self) -> Self;
+    fn from_le(value: Self) -> Self;
+    fn to_be(self) -> Self;
+    fn from_be(value: Self) -> Self;
+}
+
+macro_rules! impl_fixed_int {
+    ($($ty:ty),*) => {$(
+        impl sealed::Sealed for $ty {}
+
+        // SAFETY: primitive integers are plain old data
+        unsafe impl FixedInt for $ty {
+            fn to_le(self) -> Self {
+                <$ty>::to_le(self)
+            }
+
+            fn from_le(value: Self) -> Self {
+                <$ty>::from_le(value)
+            }
+
+            fn to_be(self) -> Self {
+                <$ty>::to_be(self)
+            }
+
+            fn from_be(value: Self) -> Self {
+                <$ty>::from_be(value)
+            }
+        }
+    )*};
+}
+
+impl_fixed_int!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Checks that `bytes` can be reinterpreted as a slice of `S`
+fn check_cast<S>(bytes: &[u8]) -> Result<usize, FromBytesError> {
+    let size = size_of::<S>();
+
+    if !bytes.len().is_multiple_of(size) {
+        return Err(FromBytesError::Length {
+            len: bytes.len(),
+            size,
+        });
+    }
+
+    if !bytes.as_ptr().cast::<S>().is_aligned() {
+        return Err(FromBytesError::Misaligned);
+    }
+
+    Ok(bytes.len() / size)
+}
+
+macro_rules! endian_span {
+    ($(#[$attr:meta])* $name:ident, repr($($repr:ident),*), $to:ident, $from:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr($($repr),*)]
+        pub struct $name<T: FixedInt> {
+            start: T,
+            end: T,
+        }
+
+        impl<T: FixedInt> $name<T> {
+            #[must_use]
+            pub fn new(span: Span<T>) -> Self {
+                Self {
+                    start: span.start.$to(),
+                    end: span.end.$to(),
+                }
+            }
+
+            /// Converts this span to a native span
+            #[must_use]
+            pub fn get(self) -> Span<T> {
+                Span {
+                    start: T::$from(self.start),
+                    end: T::$from(self.end),
+                }
+            }
+
+            pub fn set(&mut self, span: Span<T>) {
+                *self = Self::new(span);
+            }
+
+            /// Reinterprets bytes as spans without copying them
+            pub fn cast_slice(bytes: &[u8]) -> Result<&[Self], FromBytesError> {
+                // The pointer of an empty slice doesn't need to be aligned
+                if bytes.is_empty() {
+                    return Ok(&[]);
+                }
+
+                let len = check_cast::<Self>(bytes)?;
+
+                // SAFETY: `check_cast` checked the length and alignment, and
+                // every bit pattern is a valid span because `T: FixedInt`
+                Ok(unsafe { slice::from_raw_parts(bytes.as_ptr().cast(), len) })
+            }
+
+            /// Reinterprets bytes as mutable spans without copying them
+            pub fn cast_slice_mut(bytes: &mut [u8]) -> Result<&mut [Self], FromBytesError> {
+                if bytes.is_empty() {
+                    return Ok(&mut []);
+                }
+
+                let len = check_cast::<Self>(bytes)?;
+
+                // SAFETY: see `cast_slice`
+                Ok(unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), len) })
+            }
+
+            /// Reads spans from bytes. The bytes are only copied if they aren't
+            /// aligned for `Self`.
+            #[cfg(feature = "alloc")]
+            pub fn read_slice(bytes: &[u8]) -> Result<Cow<'_, [Self]>, FromBytesError> {
+                match Self::cast_slice(bytes) {
+                    Ok(spans) => Ok(Cow::Borrowed(spans)),
+                    Err(FromBytesError::Misaligned) => Ok(Cow::Owned(
+                        bytes
+                            .chunks_exact(size_of::<Self>())
+                            // SAFETY: each chunk is exactly the size of a span,
+                            // and every bit pattern is a valid span
+                            .map(|chunk| unsafe { chunk.as_ptr().cast::<Self>().read_unaligned() })
+                            .collect::<Vec<_>>(),
+                    )),
+                    Err(err) => Err(err),
+                }
+            }
+
+            /// Views spans as their underlying bytes
+            #[must_use]
+            pub fn as_bytes(spans: &[Self]) -> &[u8] {
+                // SAFETY: spans have no padding because `T: FixedInt` and both
+                // fields have the same type
+                unsafe { slice::from_raw_parts(spans.as_ptr().cast(), size_of_val(spans)) }
+            }
+
+            /// Views spans as their underlying bytes mutably
+            #[must_use]
+            pub fn as_bytes_mut(spans: &mut [Self]) -> &mut [u8] {
+                // SAFETY: see `as_bytes`. Any bytes written are a valid span.
+                unsafe { slice::from_raw_parts_mut(spans.as_mut_ptr().cast(), size_of_val(spans)) }
+            }
+        }
+
+        impl<T: FixedInt> Default for $name<T> {
+            fn default() -> Self {
+                Self::new(Span::default())
+            }
+        }
+
+        impl<T: FixedInt + Debug> Debug for $name<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Debug::fmt(&self.get().range(), f)
+            }
+        }
+
+        impl<T: FixedInt> From<Span<T>> for $name<T> {
+            fn from(value: Span<T>) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl<T: FixedInt> From<$name<T>> for Span<T> {
+            fn from(value: $name<T>) -> Self {
+                value.get()
+            }
+        }
+    };
+}
+
+endian_span!(
+    /// A span that is stored with little-endian integers, for use in files
+    /// that are shared between machines.
+    /// ```rust
+    /// # use copyspan::{Span, SpanLe};
+    /// let mut spans = [SpanLe::new(Span::from(3..7u32)); 2];
+    /// spans[1].set(Span::from(10..12));
+    ///
+    /// let bytes = SpanLe::as_bytes(&spans);
+    /// assert_eq!(bytes[..8], [3, 0, 0, 0, 7, 0, 0, 0]);
+    ///
+    /// let read = SpanLe::<u32>::read_slice(bytes).unwrap();
+    /// assert_eq!(read[1].get(), Span::from(10..12));
+    ///
+    /// // Empty bytes are never misaligned
+    /// assert_eq!(SpanLe::<u32>::cast_slice(&[]), Ok(&[][..]));
+    /// assert_eq!(SpanLe::<u32>::cast_slice(&bytes[1..1]), Ok(&[][..]));
+    /// assert!(SpanLe::<u32>::read_slice(&bytes[1..1]).unwrap().is_empty());
+    /// ```
+    ///
+    /// The bounds must have the same size on every target, so `usize` and
+    /// `isize` aren't allowed.
+    /// ```rust,compile_fail
+    /// # use copyspan::{Span, SpanLe};
+    /// let span = SpanLe::new(Span::from(3..7usize));
+    /// ```
+    SpanLe, repr(C), to_le, from_le
+);
+
+endian_span!(
+    /// A span that is stored with big-endian integers, for use in files that
+    /// are shared between machines
+    SpanBe, repr(C), to_be, from_be
+);
+
+endian_span!(
+    /// A [`SpanLe`] with an alignment of one, so it can be read from any
+    /// position in a byte buffer.
+    /// ```rust
+    /// # use copyspan::{Span, UnalignedSpanLe};
+    /// let bytes = [0xff, 3, 0, 0, 0, 7, 0, 0, 0];
+    ///
+    /// let spans = UnalignedSpanLe::<u32>::cast_slice(&bytes[1..]).unwrap();
+    /// assert_eq!(spans[0].get(), Span::from(3..7));
+    /// ```
+    UnalignedSpanLe, repr(C, packed), to_le, from_le
+);
+
+endian_span!(
+    /// A [`SpanBe`] with an alignment of one, so it can be read from any
+    /// position in a byte buffer
+    UnalignedSpanBe, repr(C, packed), to_be, from_be
+);
@@ -0,0 +1,56 @@
+use core::fmt::{self, Display};
+
+/// An error caused by an invalid [`Span`](crate::Span)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpanError {
+    /// The span starts after it ends
+    Inverted,
+    /// The span extends past the end of something with length `len`
+    OutOfBounds { len: usize },
+    /// The span starts or ends inside of a multi-byte character
+    NotCharBoundary { at: usize },
+    /// A position in the span can't be represented in the required integer
+    /// type
+    Overflow,
+    /// A range has no start or no end, so it can't be turned into a span
+    Unbounded,
+}
+
+impl Display for SpanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inverted => write!(f, "span starts after it ends"),
+            Self::OutOfBounds { len } => write!(f, "span is out of bounds for length {len}"),
+            Self::NotCharBoundary { at } => write!(f, "byte index {at} is not a char boundary"),
+            Self::Overflow => write!(f, "span position is out of range for its target type"),
+            Self::Unbounded => write!(f, "range is unbounded"),
+        }
+    }
+}
+
+impl core::error::Error for SpanError {}
+
+/// An error caused by reinterpreting a byte slice as a slice of spans
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FromBytesError {
+    /// The bytes aren't aligned for the span type
+    Misaligned,
+    /// The number of bytes, `len`, isn't a multiple of the span's `size`
+    Length { len: usize, size: usize },
+}
+
+impl Display for FromBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Misaligned => write!(f, "bytes are not aligned for the span type"),
+            Self::Length { len, size } => {
+                write!(
+                    f,
+                    "byte length {len} is not a multiple of the span size {size}"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for FromBytesError {}
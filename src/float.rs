@@ -0,0 +1,216 @@
+use core::{
+    cmp::Ordering,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    ops::{Add, Mul, Range, Sub},
+};
+
+/// A primitive float
+#[doc(hidden)]
+pub trait Float:
+    Copy
+    + Default
+    + PartialOrd
+    + Debug
+    + Add<Self, Output = Self>
+    + Sub<Self, Output = Self>
+    + Mul<Self, Output = Self>
+{
+    fn is_nan(self) -> bool;
+    fn total_cmp(&self, other: &Self) -> Ordering;
+    fn to_bits(self) -> u64;
+}
+
+macro_rules! impl_float {
+    ($ty:ty) => {
+        impl Float for $ty {
+            fn is_nan(self) -> bool {
+                <$ty>::is_nan(self)
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                <$ty>::total_cmp(self, other)
+            }
+
+            fn to_bits(self) -> u64 {
+                <$ty>::to_bits(self).into()
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
+
+/// A half-open span of floating point numbers, from `start` up to but not
+/// including `end`.
+///
+/// A span with a NaN end doesn't contain or overlap with anything. Arithmetic
+/// such as [`FloatSpan::len`] and [`FloatSpan::lerp`] follows the usual float
+/// rules, so NaN ends produce NaN results.
+///
+/// Equality, ordering and hashing use [`f64::total_cmp`], so spans can be used
+/// as keys in maps. This means that `-0.0` and `0.0` are different, and that
+/// NaN ends with the same bits are equal.
+/// ```rust
+/// # use copyspan::FloatSpan;
+/// let beat = FloatSpan::from(0.5..1.0);
+///
+/// assert!(beat.contains(0.5));
+/// assert!(!beat.contains(1.0));
+/// assert_eq!(beat.len(), 0.5);
+/// assert_eq!(beat.lerp(0.5), 0.75);
+/// assert_eq!(beat.clamp(2.0), 1.0);
+///
+/// assert_eq!(beat.intersection(FloatSpan::from(0.75..2.0)), Some(FloatSpan::from(0.75..1.0)));
+/// assert_eq!(beat.intersection(FloatSpan::from(1.0..2.0)), None);
+///
+/// assert!(!FloatSpan::from(0.0..f64::NAN).contains(0.5));
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(C)]
+pub struct FloatSpan<F: Float = f64> {
+    pub start: F,
+    pub end: F,
+}
+
+impl<F: Float> FloatSpan<F> {
+    /// Whether either end of this span is NaN
+    #[must_use]
+    pub fn is_nan(&self) -> bool {
+        self.start.is_nan() || self.end.is_nan()
+    }
+
+    /// Whether `value` is at least `start` and less than `end`. This is false
+    /// if `value` or either end is NaN.
+    #[must_use]
+    pub fn contains(&self, value: F) -> bool {
+        !self.is_nan() && self.start <= value && value < self.end
+    }
+
+    /// Whether this span contains no values. Spans that end before they start
+    /// or have a NaN end are empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.start.partial_cmp(&self.end) != Some(Ordering::Less)
+    }
+
+    /// Checks if this span overlaps with another span. This works the same way
+    /// as [`Span::overlaps_with`](crate::Span::overlaps_with), so a zero-width
+    /// span overlaps with a span that it is inside of.
+    #[must_use]
+    pub fn overlaps_with(&self, other: Self) -> bool {
+        self.contains(other.start) || other.contains(self.start)
+    }
+
+    /// The values that are in both spans. This is `Some` exactly when
+    /// [`FloatSpan::overlaps_with`] is true.
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        // Overlapping spans never have NaN ends, so these comparisons are exact
+        self.overlaps_with(other).then(|| Self {
+            start: if self.start < other.start {
+                other.start
+            } else {
+                self.start
+            },
+            end: if self.end < other.end {
+                self.end
+            } else {
+                other.end
+            },
+        })
+    }
+
+    /// The distance from `start` to `end`. This is negative for spans that end
+    /// before they start.
+    #[must_use]
+    pub fn len(&self) -> F {
+        self.end - self.start
+    }
+
+    /// Linearly interpolates between the ends of this span, giving `start` for
+    /// `0.0` and `end` for `1.0`. Values of `t` outside of `0.0..=1.0`
+    /// extrapolate.
+    #[must_use]
+    pub fn lerp(&self, t: F) -> F {
+        self.start + self.len() * t
+    }
+
+    /// Clamps `value` to be between `start` and `end`, including `end`.
+    ///
+    /// Unlike [`f64::clamp`], this doesn't panic. A NaN `value` stays NaN,
+    /// NaN ends are ignored, and values below `start` become `start` even if
+    /// the span ends before it starts.
+    // This takes `self` by value so that `Ord::clamp` doesn't shadow it
+    #[must_use]
+    pub fn clamp(self, value: F) -> F {
+        if value < self.start {
+            self.start
+        } else if value > self.end {
+            self.end
+        } else {
+            value
+        }
+    }
+
+    #[must_use]
+    pub const fn range(self) -> Range<F> {
+        Range {
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+impl<F: Float> Debug for FloatSpan<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.range(), f)
+    }
+}
+
+impl<F: Float> PartialEq for FloatSpan<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<F: Float> Eq for FloatSpan<F> {}
+
+/// Spans are ordered by their start and then by their end, using
+/// [`f64::total_cmp`]
+impl<F: Float> Ord for FloatSpan<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .total_cmp(&other.start)
+            .then_with(|| self.end.total_cmp(&other.end))
+    }
+}
+
+impl<F: Float> PartialOrd for FloatSpan<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: Float> Hash for FloatSpan<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.start.to_bits().hash(state);
+        self.end.to_bits().hash(state);
+    }
+}
+
+impl<F: Float> From<Range<F>> for FloatSpan<F> {
+    fn from(value: Range<F>) -> Self {
+        Self {
+            start: value.start,
+            end: value.end,
+        }
+    }
+}
+
+impl<F: Float> From<FloatSpan<F>> for Range<F> {
+    fn from(value: FloatSpan<F>) -> Self {
+        value.range()
+    }
+}
@@ -0,0 +1,196 @@
+use core::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, Sub},
+};
+
+/// A type that can be used as the start and end of a [`Span`](crate::Span).
+///
+/// This is implemented for the primitive integers up to 64 bits. Newtype
+/// indices can implement it with `#[derive(SpanIndex)]` when the `derive`
+/// feature is enabled, or by hand:
+/// ```rust
+/// # use copyspan::{Span, SpanIndex};
+/// # use std::ops::{Add, Sub};
+/// #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// struct TokenIdx(u32);
+///
+/// impl Add for TokenIdx {
+///     type Output = Self;
+///
+///     fn add(self, rhs: Self) -> Self {
+///         Self(self.0 + rhs.0)
+///     }
+/// }
+///
+/// impl Sub for TokenIdx {
+///     type Output = Self;
+///
+///     fn sub(self, rhs: Self) -> Self {
+///         Self(self.0 - rhs.0)
+///     }
+/// }
+///
+/// impl SpanIndex for TokenIdx {
+///     const ZERO: Self = Self(0);
+///     const ONE: Self = Self(1);
+///     const MIN: Self = Self(u32::MIN);
+///     const MAX: Self = Self(u32::MAX);
+///
+///     fn to_i128(self) -> i128 {
+///         self.0.into()
+///     }
+///
+///     fn from_i128(value: i128) -> Option<Self> {
+///         u32::try_from(value).ok().map(Self)
+///     }
+/// }
+///
+/// let tokens = ["let", "x", "=", "1"];
+/// let span = Span::from(TokenIdx(1)..TokenIdx(3));
+///
+/// assert_eq!(span.len(), TokenIdx(2));
+/// assert_eq!(&tokens[span], ["x", "="]);
+/// ```
+///
+/// The provided methods are implemented in terms of [`SpanIndex::to_i128`]
+/// and [`SpanIndex::from_i128`], so they only need to be overridden for speed.
+pub trait SpanIndex:
+    Copy + Default + Ord + Hash + Debug + Add<Self, Output = Self> + Sub<Self, Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    /// Converts this index to an `i128` without losing any information
+    fn to_i128(self) -> i128;
+
+    /// Converts an `i128` to an index, or returns `None` if it is out of range
+    fn from_i128(value: i128) -> Option<Self>;
+
+    /// Converts this index to a `usize`, or returns `None` if it is out of
+    /// range
+    fn to_usize(self) -> Option<usize> {
+        usize::try_from(self.to_i128()).ok()
+    }
+
+    /// Converts a `usize` to an index, or returns `None` if it is out of range
+    fn from_usize(value: usize) -> Option<Self> {
+        i128::try_from(value).ok().and_then(Self::from_i128)
+    }
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self::from_i128(self.to_i128().checked_add(rhs.to_i128())?)
+    }
+
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Self::from_i128(self.to_i128().checked_sub(rhs.to_i128())?)
+    }
+
+    fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or(if rhs < Self::ZERO {
+            Self::MIN
+        } else {
+            Self::MAX
+        })
+    }
+
+    fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or(if rhs < Self::ZERO {
+            Self::MAX
+        } else {
+            Self::MIN
+        })
+    }
+
+    /// Adds two indices, wrapping around between [`SpanIndex::MIN`] and
+    /// [`SpanIndex::MAX`]
+    fn wrapping_add(self, rhs: Self) -> Self {
+        wrap(self.to_i128() + rhs.to_i128())
+    }
+
+    /// Subtracts two indices, wrapping around between [`SpanIndex::MIN`] and
+    /// [`SpanIndex::MAX`]
+    fn wrapping_sub(self, rhs: Self) -> Self {
+        wrap(self.to_i128() - rhs.to_i128())
+    }
+}
+
+fn wrap<T: SpanIndex>(value: i128) -> T {
+    let min = T::MIN.to_i128();
+    let count = T::MAX.to_i128() - min + 1;
+
+    T::from_i128((value - min).rem_euclid(count) + min).expect("index range is not contiguous")
+}
+
+/// A [`SpanIndex`] that counts UTF-8 bytes.
+///
+/// Only spans of a `ByteIndex` can index a `str`. This is implemented for the
+/// primitive integers, and for newtypes that use `#[span_index(bytes)]` with
+/// `#[derive(SpanIndex)]`.
+pub trait ByteIndex: SpanIndex {}
+
+macro_rules! impl_spanindex {
+    ($ty:ty) => {
+        impl SpanIndex for $ty {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MIN: Self = <$ty>::MIN;
+            const MAX: Self = <$ty>::MAX;
+
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            fn from_i128(value: i128) -> Option<Self> {
+                <$ty>::try_from(value).ok()
+            }
+
+            fn to_usize(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
+
+            fn from_usize(value: usize) -> Option<Self> {
+                <$ty>::try_from(value).ok()
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$ty>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$ty>::checked_sub(self, rhs)
+            }
+
+            fn saturating_add(self, rhs: Self) -> Self {
+                <$ty>::saturating_add(self, rhs)
+            }
+
+            fn saturating_sub(self, rhs: Self) -> Self {
+                <$ty>::saturating_sub(self, rhs)
+            }
+
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$ty>::wrapping_add(self, rhs)
+            }
+
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                <$ty>::wrapping_sub(self, rhs)
+            }
+        }
+
+        impl ByteIndex for $ty {}
+    };
+}
+
+impl_spanindex!(u8);
+impl_spanindex!(u16);
+impl_spanindex!(u32);
+impl_spanindex!(u64);
+impl_spanindex!(usize);
+impl_spanindex!(i8);
+impl_spanindex!(i16);
+impl_spanindex!(i32);
+impl_spanindex!(i64);
+impl_spanindex!(isize);
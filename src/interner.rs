@@ -0,0 +1,217 @@
+use alloc::vec::Vec;
+use core::{any::TypeId, cell::RefCell, fmt::Debug, hash::Hash};
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock, RwLockReadGuard},
+};
+
+use crate::{Span, SpanIndex};
+
+/// A handle to a span stored in a [`SpanInterner`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct SpanId(pub(crate) u32);
+
+impl SpanId {
+    /// The position of this span in the order that spans were interned
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Hands out small [`SpanId`]s for spans along with some context data `C`,
+/// such as a file or macro expansion.
+///
+/// Interning the same span and context twice gives the same id. The interner
+/// can be shared between threads by reference, and it can be made available
+/// to the code inside of a closure with [`SpanInterner::scope`]. A
+/// `SpanInterner<u32>` also stores the long spans of
+/// [`PackedSpan`](crate::PackedSpan).
+/// ```rust
+/// # use copyspan::{Span, SpanInterner};
+/// let interner = SpanInterner::<u32, &str>::new();
+///
+/// let a = interner.intern_with(Span::from(0..5), "main.rs");
+/// let b = interner.intern_with(Span::from(0..5), "lib.rs");
+///
+/// assert_ne!(a, b);
+/// assert_eq!(a, interner.intern_with(Span::from(0..5), "main.rs"));
+/// assert_eq!(interner.get(b), (Span::from(0..5), "lib.rs"));
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| interner.intern_with(Span::from(5..9), "main.rs"));
+/// });
+/// assert_eq!(interner.len(), 3);
+/// ```
+pub struct SpanInterner<T: SpanIndex = usize, C = ()> {
+    inner: RwLock<Inner<T, C>>,
+}
+
+struct Inner<T: SpanIndex, C> {
+    entries: Vec<(Span<T>, C)>,
+    ids: HashMap<(Span<T>, C), SpanId>,
+}
+
+impl<T: SpanIndex, C: Clone + Eq + Hash> SpanInterner<T, C> {
+    /// Creates an empty interner
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: RwLock::new(Inner {
+                entries: Vec::new(),
+                ids: HashMap::new(),
+            }),
+        }
+    }
+
+    /// The number of distinct entries that have been interned
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.read().entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Interns a span with some context data
+    ///
+    /// # Panics
+    /// Panics if `u32::MAX` entries have already been interned.
+    pub fn intern_with(&self, span: Span<T>, ctx: C) -> SpanId {
+        let key = (span, ctx);
+
+        if let Some(&id) = self.read().ids.get(&key) {
+            return id;
+        }
+
+        let mut inner = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+
+        // Another thread may have interned the same entry between the two locks
+        if let Some(&id) = inner.ids.get(&key) {
+            return id;
+        }
+
+        let id = SpanId(u32::try_from(inner.entries.len()).expect("too many interned spans"));
+        inner.entries.push(key.clone());
+        inner.ids.insert(key, id);
+
+        id
+    }
+
+    /// Gets the span and context data for an id.
+    ///
+    /// # Panics
+    /// Panics if `id` was created by a different interner.
+    #[must_use]
+    pub fn get(&self, id: SpanId) -> (Span<T>, C) {
+        self.read().entries[id.index()].clone()
+    }
+
+    /// Gets the span for an id.
+    ///
+    /// # Panics
+    /// Panics if `id` was created by a different interner.
+    #[must_use]
+    pub fn span(&self, id: SpanId) -> Span<T> {
+        self.read().entries[id.index()].0
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Inner<T, C>> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: SpanIndex, C: Clone + Eq + Hash + Default> SpanInterner<T, C> {
+    /// Interns a span with the default context data
+    pub fn intern(&self, span: Span<T>) -> SpanId {
+        self.intern_with(span, C::default())
+    }
+}
+
+impl<T: SpanIndex, C: Clone + Eq + Hash> Default for SpanInterner<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SpanIndex, C: Debug> Debug for SpanInterner<T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let inner = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+
+        // Keyed by id index
+        f.debug_map()
+            .entries(inner.entries.iter().enumerate())
+            .finish()
+    }
+}
+
+std::thread_local! {
+    /// The interners that are in scope on this thread, innermost last
+    static SCOPED: RefCell<Vec<(TypeId, *const ())>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Removes an interner from the scope when dropped, even if the scoped closure
+/// panics
+struct ScopeGuard;
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPED.with_borrow_mut(|scoped| scoped.pop());
+    }
+}
+
+impl<T: SpanIndex + 'static, C: Clone + Eq + Hash + 'static> SpanInterner<T, C> {
+    /// Makes this interner available through [`SpanInterner::with_current`]
+    /// on this thread while `f` runs.
+    ///
+    /// Scopes can be nested. Other threads can share the same interner by
+    /// calling `scope` on it themselves.
+    /// ```rust
+    /// # use copyspan::{Span, SpanInterner};
+    /// fn parse() -> copyspan::SpanId {
+    ///     SpanInterner::<u32>::with_current(|interner| interner.intern(Span::from(3..7)))
+    /// }
+    ///
+    /// let interner = SpanInterner::<u32>::new();
+    /// let id = interner.scope(parse);
+    ///
+    /// assert_eq!(interner.span(id), Span::from(3..7));
+    /// assert!(SpanInterner::<u32>::try_with_current(|_| ()).is_none());
+    /// ```
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        let entry = (TypeId::of::<Self>(), (self as *const Self).cast::<()>());
+        SCOPED.with_borrow_mut(|scoped| scoped.push(entry));
+
+        let _guard = ScopeGuard;
+        f()
+    }
+
+    /// Calls `f` with the innermost interner of this type that is in scope on
+    /// this thread, or returns `None` if there isn't one
+    pub fn try_with_current<R>(f: impl FnOnce(&Self) -> R) -> Option<R> {
+        let ptr = SCOPED.with_borrow(|scoped| {
+            scoped
+                .iter()
+                .rev()
+                .find(|(ty, _)| *ty == TypeId::of::<Self>())
+                .map(|(_, ptr)| ptr.cast::<Self>())
+        })?;
+
+        // SAFETY: the pointer was created from a reference of this type in
+        // `scope`, and it is removed from `SCOPED` before that reference's
+        // borrow ends
+        Some(f(unsafe { &*ptr }))
+    }
+
+    /// Calls `f` with the innermost interner of this type that is in scope on
+    /// this thread.
+    ///
+    /// # Panics
+    /// Panics if no interner of this type is in scope.
+    pub fn with_current<R>(f: impl FnOnce(&Self) -> R) -> R {
+        Self::try_with_current(f).expect("no span interner of this type is in scope")
+    }
+}
@@ -0,0 +1,555 @@
+use core::{
+    fmt::Debug,
+    ops::{Add, Div, Mul, Sub},
+};
+
+use crate::{Span, SpanError, SpanIndex};
+
+/// Which way to round a result that can't be represented exactly
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub enum Round {
+    Down,
+    Up,
+}
+
+/// A number that can be a bound of an [`Interval`].
+///
+/// Each operation returns `None` if its result doesn't fit in `Self`, and
+/// otherwise rounds in the requested direction.
+#[doc(hidden)]
+pub trait IntervalBound: Copy + PartialOrd + Debug {
+    const ZERO: Self;
+
+    fn add(self, rhs: Self, round: Round) -> Option<Self>;
+    fn sub(self, rhs: Self, round: Round) -> Option<Self>;
+    fn mul(self, rhs: Self, round: Round) -> Option<Self>;
+    /// Only called with a nonzero `rhs`
+    fn div(self, rhs: Self, round: Round) -> Option<Self>;
+    /// Only called with a non-negative `self`
+    fn sqrt(self, round: Round) -> Option<Self>;
+    fn neg(self) -> Option<Self>;
+
+    /// Divides by an interval that contains zero but isn't `[0, 0]`
+    fn div_across_zero(lhs: Interval<Self>, rhs: Interval<Self>) -> Option<Interval<Self>>;
+}
+
+/// Integer division rounded towards negative or positive infinity
+fn div_round(lhs: i128, rhs: i128, round: Round) -> i128 {
+    let quotient = lhs / rhs;
+    let inexact = lhs % rhs != 0;
+    let negative = (lhs < 0) != (rhs < 0);
+
+    match round {
+        Round::Down if inexact && negative => quotient - 1,
+        Round::Up if inexact && !negative => quotient + 1,
+        _ => quotient,
+    }
+}
+
+macro_rules! impl_int_bound {
+    ($ty:ty) => {
+        impl IntervalBound for $ty {
+            const ZERO: Self = 0;
+
+            fn add(self, rhs: Self, _: Round) -> Option<Self> {
+                self.checked_add(rhs)
+            }
+
+            fn sub(self, rhs: Self, _: Round) -> Option<Self> {
+                self.checked_sub(rhs)
+            }
+
+            fn mul(self, rhs: Self, _: Round) -> Option<Self> {
+                self.checked_mul(rhs)
+            }
+
+            fn div(self, rhs: Self, round: Round) -> Option<Self> {
+                let quotient = div_round(self.to_i128(), rhs.to_i128(), round);
+                Self::from_i128(quotient)
+            }
+
+            fn sqrt(self, round: Round) -> Option<Self> {
+                let root = self.isqrt();
+
+                match round {
+                    Round::Up if root * root < self => Some(root + 1),
+                    _ => Some(root),
+                }
+            }
+
+            fn neg(self) -> Option<Self> {
+                Self::from_i128(-self.to_i128())
+            }
+
+            fn div_across_zero(lhs: Interval<Self>, rhs: Interval<Self>) -> Option<Interval<Self>> {
+                // Integers can't be between zero and one, so only the nonzero
+                // parts of the divisor matter
+                let mut result = None;
+
+                if rhs.lo < <Self as IntervalBound>::ZERO {
+                    let neg_one = Self::from_i128(-1)?;
+                    result = Some(lhs.div_nonzero(Interval::new_unchecked(rhs.lo, neg_one))?);
+                }
+
+                if rhs.hi > <Self as IntervalBound>::ZERO {
+                    let above = lhs.div_nonzero(Interval::new_unchecked(1, rhs.hi))?;
+                    result = Some(result.map_or(above, |below: Interval<Self>| below.hull(above)));
+                }
+
+                result
+            }
+        }
+    };
+}
+
+impl_int_bound!(u8);
+impl_int_bound!(u16);
+impl_int_bound!(u32);
+impl_int_bound!(u64);
+impl_int_bound!(usize);
+impl_int_bound!(i8);
+impl_int_bound!(i16);
+impl_int_bound!(i32);
+impl_int_bound!(i64);
+impl_int_bound!(isize);
+
+#[cfg(feature = "std")]
+macro_rules! impl_float_bound {
+    ($ty:ty) => {
+        impl IntervalBound for $ty {
+            const ZERO: Self = 0.0;
+
+            fn add(self, rhs: Self, round: Round) -> Option<Self> {
+                let sum = self + rhs;
+
+                // The exact rounding error of the sum
+                let rhs_part = sum - self;
+                let error = (self - (sum - rhs_part)) + (rhs - rhs_part);
+
+                Some(directed(sum, error, round))
+            }
+
+            fn sub(self, rhs: Self, round: Round) -> Option<Self> {
+                IntervalBound::add(self, -rhs, round)
+            }
+
+            fn mul(self, rhs: Self, round: Round) -> Option<Self> {
+                // Zero times infinity is zero for interval bounds
+                if self == 0.0 || rhs == 0.0 {
+                    return Some(0.0);
+                }
+
+                let product = self * rhs;
+                Some(directed(product, self.mul_add(rhs, -product), round))
+            }
+
+            fn div(self, rhs: Self, round: Round) -> Option<Self> {
+                let quotient = self / rhs;
+                let remainder = (-quotient).mul_add(rhs, self);
+
+                Some(directed(quotient, remainder * rhs.signum(), round))
+            }
+
+            fn sqrt(self, round: Round) -> Option<Self> {
+                let root = <$ty>::sqrt(self);
+                Some(directed(root, (-root).mul_add(root, self), round))
+            }
+
+            fn neg(self) -> Option<Self> {
+                Some(-self)
+            }
+
+            fn div_across_zero(lhs: Interval<Self>, rhs: Interval<Self>) -> Option<Interval<Self>> {
+                let entire = Interval::new_unchecked(<$ty>::NEG_INFINITY, <$ty>::INFINITY);
+
+                let interval = if lhs.hi < 0.0 && rhs.hi == 0.0 {
+                    Interval::new_unchecked(
+                        IntervalBound::div(lhs.hi, rhs.lo, Round::Down)?,
+                        <$ty>::INFINITY,
+                    )
+                } else if lhs.hi < 0.0 && rhs.lo == 0.0 {
+                    Interval::new_unchecked(
+                        <$ty>::NEG_INFINITY,
+                        IntervalBound::div(lhs.hi, rhs.hi, Round::Up)?,
+                    )
+                } else if lhs.lo > 0.0 && rhs.hi == 0.0 {
+                    Interval::new_unchecked(
+                        <$ty>::NEG_INFINITY,
+                        IntervalBound::div(lhs.lo, rhs.lo, Round::Up)?,
+                    )
+                } else if lhs.lo > 0.0 && rhs.lo == 0.0 {
+                    Interval::new_unchecked(
+                        IntervalBound::div(lhs.lo, rhs.hi, Round::Down)?,
+                        <$ty>::INFINITY,
+                    )
+                } else {
+                    entire
+                };
+
+                Some(interval)
+            }
+        }
+    };
+}
+
+/// Rounds `value` away from the exact result if `error`, the exact result
+/// minus `value`, says that it was rounded the wrong way
+#[cfg(feature = "std")]
+fn directed<F: Into<f64> + Copy + FloatStep>(value: F, error: F, round: Round) -> F {
+    let error: f64 = error.into();
+
+    match round {
+        Round::Down if error < 0.0 || error.is_nan() => value.step_down(),
+        Round::Up if error > 0.0 || error.is_nan() => value.step_up(),
+        _ => value,
+    }
+}
+
+#[cfg(feature = "std")]
+trait FloatStep {
+    fn step_down(self) -> Self;
+    fn step_up(self) -> Self;
+}
+
+#[cfg(feature = "std")]
+macro_rules! impl_float_step {
+    ($ty:ty) => {
+        impl FloatStep for $ty {
+            fn step_down(self) -> Self {
+                if self.is_nan() {
+                    self
+                } else {
+                    self.next_down()
+                }
+            }
+
+            fn step_up(self) -> Self {
+                if self.is_nan() { self } else { self.next_up() }
+            }
+        }
+    };
+}
+
+// `mul_add` and `sqrt` on floats need `std`
+#[cfg(feature = "std")]
+impl_float_step!(f32);
+#[cfg(feature = "std")]
+impl_float_step!(f64);
+#[cfg(feature = "std")]
+impl_float_bound!(f32);
+#[cfg(feature = "std")]
+impl_float_bound!(f64);
+
+/// A closed interval of numbers, from `lo` up to and including `hi`.
+///
+/// Arithmetic on intervals gives an interval that contains every result of
+/// applying the operation to a number from each interval. Results that can't
+/// be represented exactly are rounded outward, so the true result is always
+/// inside of the returned interval:
+/// ```rust
+/// # use copyspan::Interval;
+/// let x = Interval::new(1, 3).unwrap();
+/// let y = Interval::new(-2, 4).unwrap();
+///
+/// assert_eq!(x + y, Interval::new(-1, 7).unwrap());
+/// assert_eq!(x - y, Interval::new(-3, 5).unwrap());
+/// assert_eq!(x * y, Interval::new(-6, 12).unwrap());
+/// assert_eq!(x / Interval::new(2, 2).unwrap(), Interval::new(0, 2).unwrap());
+/// assert_eq!(y.abs(), Interval::new(0, 4).unwrap());
+///
+/// // Integer overflow is checked
+/// let big = Interval::<u8>::new(200, 250).unwrap();
+/// assert_eq!(big.checked_add(Interval::new(1, 10).unwrap()), None);
+/// ```
+///
+/// Dividing by an interval that contains zero gives an interval that covers
+/// every quotient from the nonzero part of the divisor. Dividing by `[0, 0]`
+/// returns `None`.
+/// ```rust
+/// # use copyspan::Interval;
+/// let x = Interval::new(6, 12).unwrap();
+/// assert_eq!(x.checked_div(Interval::new(-2, 3).unwrap()), Interval::new(-12, 12).ok());
+/// assert_eq!(x.checked_div(Interval::point(0)), None);
+/// ```
+#[cfg_attr(
+    feature = "std",
+    doc = r#"
+With the `std` feature, the bounds can also be `f32` or `f64`. Dividing a
+float interval by an interval that contains zero gives an unbounded interval,
+and float intervals with NaN bounds may give NaN bounds.
+```rust
+# use copyspan::Interval;
+let third = Interval::point(1.0) / Interval::point(3.0);
+assert!(third.lo < third.hi);
+assert!(third.contains(1.0 / 3.0));
+
+let x = Interval::new(1.0, 2.0).unwrap();
+assert_eq!(x.checked_div(Interval::new(0.0, 4.0).unwrap()), Interval::new(0.25, f64::INFINITY).ok());
+```"#
+)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Interval<T: IntervalBound> {
+    pub lo: T,
+    pub hi: T,
+}
+
+impl<T: IntervalBound> Interval<T> {
+    /// Creates an interval, checking that `lo <= hi`. Intervals with NaN
+    /// bounds are treated as inverted.
+    pub fn new(lo: T, hi: T) -> Result<Self, SpanError> {
+        if lo <= hi {
+            Ok(Self { lo, hi })
+        } else {
+            Err(SpanError::Inverted)
+        }
+    }
+
+    const fn new_unchecked(lo: T, hi: T) -> Self {
+        Self { lo, hi }
+    }
+
+    /// The interval that only contains `value`
+    #[must_use]
+    pub const fn point(value: T) -> Self {
+        Self {
+            lo: value,
+            hi: value,
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        self.lo <= value && value <= self.hi
+    }
+
+    /// The smallest interval that contains both intervals
+    #[must_use]
+    pub fn hull(self, other: Self) -> Self {
+        Self {
+            lo: min(self.lo, other.lo),
+            hi: max(self.hi, other.hi),
+        }
+    }
+
+    /// Adds two intervals, or returns `None` if a bound overflows
+    #[must_use]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(Self {
+            lo: self.lo.add(rhs.lo, Round::Down)?,
+            hi: self.hi.add(rhs.hi, Round::Up)?,
+        })
+    }
+
+    /// Subtracts two intervals, or returns `None` if a bound overflows
+    #[must_use]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(Self {
+            lo: self.lo.sub(rhs.hi, Round::Down)?,
+            hi: self.hi.sub(rhs.lo, Round::Up)?,
+        })
+    }
+
+    /// Multiplies two intervals, or returns `None` if a bound overflows
+    #[must_use]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.corners(rhs, T::mul)
+    }
+
+    /// Divides two intervals, or returns `None` if a bound overflows or `rhs`
+    /// is `[0, 0]`
+    #[must_use]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.lo > T::ZERO || rhs.hi < T::ZERO {
+            self.div_nonzero(rhs)
+        } else if rhs.lo == T::ZERO && rhs.hi == T::ZERO {
+            None
+        } else {
+            T::div_across_zero(self, rhs)
+        }
+    }
+
+    fn div_nonzero(self, rhs: Self) -> Option<Self> {
+        self.corners(rhs, T::div)
+    }
+
+    /// Applies `op` to each pair of bounds and takes the smallest and largest
+    /// results
+    fn corners(self, rhs: Self, op: fn(T, T, Round) -> Option<T>) -> Option<Self> {
+        let pairs = [
+            (self.lo, rhs.lo),
+            (self.lo, rhs.hi),
+            (self.hi, rhs.lo),
+            (self.hi, rhs.hi),
+        ];
+
+        let mut lo = op(pairs[0].0, pairs[0].1, Round::Down)?;
+        let mut hi = op(pairs[0].0, pairs[0].1, Round::Up)?;
+
+        for (a, b) in &pairs[1..] {
+            lo = min(lo, op(*a, *b, Round::Down)?);
+            hi = max(hi, op(*a, *b, Round::Up)?);
+        }
+
+        Some(Self { lo, hi })
+    }
+
+    /// The interval of the smaller of a number from each interval
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            lo: min(self.lo, other.lo),
+            hi: min(self.hi, other.hi),
+        }
+    }
+
+    /// The interval of the larger of a number from each interval
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            lo: max(self.lo, other.lo),
+            hi: max(self.hi, other.hi),
+        }
+    }
+
+    /// The absolute values of the numbers in this interval, or `None` if a
+    /// bound overflows
+    #[must_use]
+    pub fn checked_abs(self) -> Option<Self> {
+        if self.lo >= T::ZERO {
+            Some(self)
+        } else if self.hi <= T::ZERO {
+            Some(Self {
+                lo: self.hi.neg()?,
+                hi: self.lo.neg()?,
+            })
+        } else {
+            Some(Self {
+                lo: T::ZERO,
+                hi: max(self.lo.neg()?, self.hi),
+            })
+        }
+    }
+
+    /// The absolute values of the numbers in this interval.
+    ///
+    /// # Panics
+    /// Panics if a bound overflows, such as with `i32::MIN`.
+    #[must_use]
+    #[track_caller]
+    pub fn abs(self) -> Self {
+        self.checked_abs().expect("attempt to negate with overflow")
+    }
+
+    /// The square roots of the non-negative numbers in this interval, or
+    /// `None` if it only contains negative numbers
+    /// ```rust
+    /// # use copyspan::Interval;
+    /// assert_eq!(Interval::new(-4, 10).unwrap().sqrt(), Interval::new(0, 4).ok());
+    /// assert_eq!(Interval::new(-4, -1).unwrap().sqrt(), None);
+    /// ```
+    #[must_use]
+    pub fn sqrt(self) -> Option<Self> {
+        if self.hi < T::ZERO {
+            return None;
+        }
+
+        Some(Self {
+            lo: max(self.lo, T::ZERO).sqrt(Round::Down)?,
+            hi: self.hi.sqrt(Round::Up)?,
+        })
+    }
+}
+
+impl<T: IntervalBound + SpanIndex> Interval<T> {
+    /// The closed interval with the same positions as a half-open span, which
+    /// is `start..=end - 1`. Returns `None` if the span is empty or inverted.
+    /// ```rust
+    /// # use copyspan::{Interval, Span};
+    /// assert_eq!(Interval::from_span(Span::from(2..5)), Interval::new(2, 4).ok());
+    /// assert_eq!(Interval::from_span(Span::from(2..2)), None);
+    /// ```
+    #[must_use]
+    pub fn from_span(span: Span<T>) -> Option<Self> {
+        (span.start < span.end).then(|| Self {
+            lo: span.start,
+            hi: span.end - T::ONE,
+        })
+    }
+
+    /// The half-open span with the same positions as this interval, which is
+    /// `lo..hi + 1`. Returns `None` if `hi + 1` overflows.
+    /// ```rust
+    /// # use copyspan::{Interval, Span};
+    /// assert_eq!(Interval::new(2, 4).unwrap().to_span(), Some(Span::from(2..5)));
+    /// assert_eq!(Interval::<u8>::new(2, 255).unwrap().to_span(), None);
+    /// ```
+    #[must_use]
+    pub fn to_span(self) -> Option<Span<T>> {
+        Some(Span {
+            start: self.lo,
+            end: self.hi.checked_add(T::ONE)?,
+        })
+    }
+}
+
+/// The smaller value, preferring `a` if the values can't be compared
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+/// The larger value, preferring `a` if the values can't be compared
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a { b } else { a }
+}
+
+impl<T: IntervalBound> Debug for Interval<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[{:?}, {:?}]", self.lo, self.hi)
+    }
+}
+
+macro_rules! impl_op {
+    ($trait:ident, $method:ident, $checked:ident, $doc:literal, $msg:literal) => {
+        /// # Panics
+        #[doc = concat!("Panics ", $doc, ". See [`Interval::", stringify!($checked), "`].")]
+        impl<T: IntervalBound> $trait for Interval<T> {
+            type Output = Self;
+
+            #[track_caller]
+            fn $method(self, rhs: Self) -> Self {
+                self.$checked(rhs).expect($msg)
+            }
+        }
+    };
+}
+
+impl_op!(
+    Add,
+    add,
+    checked_add,
+    "if a bound overflows",
+    "attempt to add with overflow"
+);
+impl_op!(
+    Sub,
+    sub,
+    checked_sub,
+    "if a bound overflows",
+    "attempt to subtract with overflow"
+);
+impl_op!(
+    Mul,
+    mul,
+    checked_mul,
+    "if a bound overflows",
+    "attempt to multiply with overflow"
+);
+impl_op!(
+    Div,
+    div,
+    checked_div,
+    "if a bound overflows or the divisor is `[0, 0]`",
+    "attempt to divide by zero or with overflow"
+);
@@ -1,5 +1,5 @@
 //! A an alternative to `Range<T>` that has a defined memory layout, implements
-//! [`std::marker::Copy`], and has some convenience methods.
+//! [`std::marker::Copy`](core::marker::Copy), and has some convenience methods.
 //!
 //! ```
 //! use copyspan::Span;
@@ -35,38 +35,149 @@
 //! expects_range(val.x.range());
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
-mod util;
+mod convert;
+#[cfg(feature = "alloc")]
+pub mod diagnostic;
+mod endian;
+mod error;
+mod float;
+mod index;
+#[cfg(feature = "std")]
+mod interner;
+mod interval;
+#[cfg(feature = "alloc")]
+pub mod line_index;
+mod option;
+#[cfg(feature = "std")]
+mod packed;
+#[cfg(any(feature = "bytemuck", feature = "zerocopy"))]
+mod pod;
+mod point;
+#[cfg(feature = "alloc")]
+pub mod range_map;
+mod relation;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "alloc")]
+pub mod source_map;
+#[cfg(feature = "alloc")]
+pub mod span_map;
+#[cfg(feature = "alloc")]
+pub mod span_set;
+pub mod spanned;
+mod unit;
 
 use core::{
     hash::{Hash, Hasher},
-    ops::{Index, IndexMut, Range},
+    ops::{
+        Add, AddAssign, BitAnd, BitOr, BitOrAssign, Bound, Index, IndexMut, Range, RangeBounds,
+        Sub, SubAssign,
+    },
 };
 
-use util::RustNumber;
+#[cfg(feature = "derive")]
+pub use copyspan_derive::SpanIndex;
+pub use endian::{SpanBe, SpanLe, UnalignedSpanBe, UnalignedSpanLe};
+pub use error::{FromBytesError, SpanError};
+pub use float::FloatSpan;
+pub use index::{ByteIndex, SpanIndex};
+#[cfg(feature = "std")]
+pub use interner::{SpanId, SpanInterner};
+pub use interval::Interval;
+#[cfg(feature = "alloc")]
+pub use line_index::LineIndex;
+pub use option::OptionSpan;
+#[cfg(feature = "std")]
+pub use packed::PackedSpan;
+pub use point::SpanPoint;
+#[cfg(feature = "alloc")]
+pub use range_map::RangeMap;
+pub use relation::IntervalRelation;
+#[cfg(feature = "alloc")]
+pub use source_map::SourceMap;
+#[cfg(feature = "alloc")]
+pub use span_map::SpanMap;
+#[cfg(feature = "alloc")]
+pub use span_set::SpanSet;
+pub use spanned::{HasSpan, Spanned};
+pub use unit::{Chars, Utf16};
 
 /// An alternative to `Range<T>` that has a defined memory layout and implements
-/// [`std::marker::Copy`].
+/// [`std::marker::Copy`](core::marker::Copy).
+#[cfg_attr(
+    feature = "zerocopy",
+    doc = r#"
+With the `zerocopy` feature, spans of integers can be converted to and from
+bytes without copying them.
+```rust
+# use copyspan::Span;
+use zerocopy::{FromBytes, IntoBytes};
+
+let span = Span::<u32>::from(3..7);
+let bytes = span.as_bytes();
+assert_eq!(bytes.len(), 8);
+
+assert_eq!(Span::<u32>::read_from_bytes(bytes), Ok(span));
+assert_eq!(Span::<u32>::ref_from_bytes(&[0; 7]).ok(), None);
+```"#
+)]
 #[repr(C)]
-pub struct Span<T: RustNumber = usize> {
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
+pub struct Span<T: SpanPoint = usize> {
     pub start: T,
     pub end: T,
 }
 
-impl<T: RustNumber> Clone for Span<T> {
+impl<T: SpanPoint> Clone for Span<T> {
     fn clone(&self) -> Self {
-        Self {
-            start: self.start,
-            end: self.end,
-        }
+        *self
     }
 }
 
-impl<T: RustNumber> Copy for Span<T> {}
+impl<T: SpanPoint> Copy for Span<T> {}
+
+impl<T: SpanPoint> Span<T> {
+    /// Creates a span, checking that it doesn't start after it ends.
+    /// ```rust
+    /// # use copyspan::{Span, SpanError};
+    /// assert_eq!(Span::new(3, 10), Ok(Span::from(3..10)));
+    /// assert_eq!(Span::new(10, 3), Err(SpanError::Inverted));
+    /// ```
+    pub fn new(start: T, end: T) -> Result<Self, SpanError> {
+        if start > end {
+            return Err(SpanError::Inverted);
+        }
+
+        Ok(Self { start, end })
+    }
+
+    /// Creates a span from its start and its length, checking that the length
+    /// isn't negative and the end doesn't overflow
+    pub fn from_start_len(start: T, len: T::Distance) -> Result<Self, SpanError> {
+        Self::at(start)
+            .checked_with_len(len)
+            .ok_or(SpanError::Overflow)
+    }
+
+    /// The start and length of this span
+    #[must_use]
+    pub fn to_start_len(self) -> (T, T::Distance) {
+        (self.start, self.len())
+    }
 
-impl<T: RustNumber> Span<T> {
     /// A zero-width span at the end of a span
     #[must_use]
     pub const fn span_after(self) -> Self {
@@ -85,15 +196,34 @@ impl<T: RustNumber> Span<T> {
         }
     }
 
-    /// Sets the length of a span without changing its start
+    /// Sets the length of a span without changing its start.
+    ///
+    /// This overflows the same way that `+` does. See
+    /// [`Span::checked_with_len`] for a version that doesn't.
     #[must_use]
-    pub fn with_len(self, len: T) -> Self {
+    pub fn with_len(self, len: T::Distance) -> Self {
         Self {
             start: self.start,
-            end: self.start + len,
+            end: self.start.add_distance(len),
         }
     }
 
+    /// Sets the length of a span without changing its start, or returns `None`
+    /// if the length is negative or the end would overflow.
+    /// ```rust
+    /// # use copyspan::Span;
+    /// assert_eq!(Span::<u8>::at(200).checked_with_len(50), Some(Span::from(200..250)));
+    /// assert_eq!(Span::<u8>::at(200).checked_with_len(60), None);
+    /// assert_eq!(Span::<i64>::at(5).checked_with_len(-2), None);
+    /// ```
+    #[must_use]
+    pub fn checked_with_len(self, len: T::Distance) -> Option<Self> {
+        Some(Self {
+            start: self.start,
+            end: self.start.checked_add_distance(len)?,
+        })
+    }
+
     /// Sets the start of a span without changing its end
     #[must_use]
     pub const fn with_start(self, start: T) -> Self {
@@ -134,7 +264,7 @@ impl<T: RustNumber> Span<T> {
 
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        &self.start == &self.end
+        self.start == self.end
     }
 
     /// Checks if this `Span` overlaps with another `Span`.
@@ -155,6 +285,143 @@ impl<T: RustNumber> Span<T> {
         self.contains(other.start) || other.contains(self.start)
     }
 
+    /// Classifies how this span relates to another span. See
+    /// [`IntervalRelation`] for how zero-width spans are handled.
+    #[must_use]
+    pub fn relation(self, other: Self) -> IntervalRelation {
+        relation::relation(self, other)
+    }
+
+    /// The number of positions in this span
+    #[must_use]
+    pub fn len(&self) -> T::Distance {
+        self.start.distance_to(self.end)
+    }
+
+    /// The number of positions in this span, or `None` if it ends before it
+    /// starts or the length overflows
+    /// ```rust
+    /// # use copyspan::Span;
+    /// assert_eq!(Span::<i32>::from(3..5).checked_len(), Some(2));
+    /// assert_eq!(Span::<i32>::from(5..3).checked_len(), None);
+    /// ```
+    #[must_use]
+    pub fn checked_len(&self) -> Option<T::Distance> {
+        if self.end < self.start {
+            return None;
+        }
+
+        self.start.checked_distance_to(self.end)
+    }
+
+    /// Checks if every position in another `Span` is also in this one.
+    /// ```rust
+    /// # use copyspan::Span;
+    /// let foo = Span::from(2..6);
+    ///
+    /// assert!(foo.contains_span(Span::from(3..6)));
+    /// assert!(foo.contains_span(Span::from(6..6)));
+    /// assert!(!foo.contains_span(Span::from(1..3)));
+    /// ```
+    #[must_use]
+    pub fn contains_span(&self, other: Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// The positions that are in both spans. This is `Some` exactly when
+    /// [`Span::overlaps_with`] is true.
+    /// ```rust
+    /// # use copyspan::Span;
+    /// let foo = Span::from(0..4);
+    ///
+    /// assert_eq!(foo.intersection(Span::from(2..6)), Some(Span::from(2..4)));
+    /// assert_eq!(foo.intersection(Span::from(2..2)), Some(Span::from(2..2)));
+    /// assert_eq!(foo.intersection(Span::from(4..6)), None);
+    /// ```
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        self.overlaps_with(other).then(|| Self {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// The smallest span that contains both spans
+    /// ```rust
+    /// # use copyspan::Span;
+    /// assert_eq!(Span::from(0..2).hull(Span::from(5..7)), Span::from(0..7));
+    /// ```
+    #[must_use]
+    pub fn hull(self, other: Self) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// The span between two spans that don't overlap. Touching spans have a
+    /// zero-width gap.
+    /// ```rust
+    /// # use copyspan::Span;
+    /// let foo = Span::from(0..2);
+    ///
+    /// assert_eq!(foo.gap_between(Span::from(5..7)), Some(Span::from(2..5)));
+    /// assert_eq!(Span::from(5..7).gap_between(foo), Some(Span::from(2..5)));
+    /// assert_eq!(foo.gap_between(Span::from(2..3)), Some(Span::from(2..2)));
+    /// assert_eq!(foo.gap_between(Span::from(1..3)), None);
+    /// ```
+    #[must_use]
+    pub fn gap_between(self, other: Self) -> Option<Self> {
+        if self.overlaps_with(other) {
+            None
+        } else if self.end <= other.start {
+            Some(Self::from(self.end..other.start))
+        } else {
+            Some(Self::from(other.end..self.start))
+        }
+    }
+
+    /// The parts of this span that come before and after another span.
+    /// ```rust
+    /// # use copyspan::Span;
+    /// let foo = Span::from(0..10);
+    ///
+    /// assert_eq!(
+    ///     foo.subtract(Span::from(3..5)),
+    ///     (Some(Span::from(0..3)), Some(Span::from(5..10))),
+    /// );
+    /// assert_eq!(foo.subtract(Span::from(8..12)), (Some(Span::from(0..8)), None));
+    /// assert_eq!(foo.subtract(Span::from(0..10)), (None, None));
+    /// ```
+    #[must_use]
+    pub fn subtract(self, other: Self) -> (Option<Self>, Option<Self>) {
+        let before = (self.start < other.start).then(|| Self {
+            start: self.start,
+            end: self.end.min(other.start),
+        });
+
+        let after = (other.end < self.end).then(|| Self {
+            start: self.start.max(other.end),
+            end: self.end,
+        });
+
+        (before, after)
+    }
+
+    /// Splits this span into the spans before and after a position.
+    ///
+    /// # Panics
+    /// Panics if `pos` is not between the start and end of this span.
+    #[must_use]
+    pub fn split_at(self, pos: T) -> (Self, Self) {
+        assert!(
+            self.start <= pos && pos <= self.end,
+            "split position is outside of the span"
+        );
+
+        (self.with_end(pos), self.with_start(pos))
+    }
+
     #[must_use]
     pub const fn range(self) -> Range<T> {
         Range {
@@ -164,35 +431,246 @@ impl<T: RustNumber> Span<T> {
     }
 }
 
+impl<T: SpanIndex> Span<T> {
+    /// Resolves any range, such as `..`, `a..` or `..=b`, to a concrete span
+    /// inside of something with length `len`.
+    ///
+    /// This fails if the resulting span starts after it ends or extends past
+    /// `len`.
+    /// ```rust
+    /// # use copyspan::{Span, SpanError};
+    /// assert_eq!(Span::from_bounds(.., 10), Ok(Span::from(0..10)));
+    /// assert_eq!(Span::from_bounds(3.., 10), Ok(Span::from(3..10)));
+    /// assert_eq!(Span::from_bounds(..=4, 10), Ok(Span::from(0..5)));
+    /// assert_eq!(Span::from_bounds(5..12, 10), Err(SpanError::OutOfBounds { len: 10 }));
+    /// ```
+    pub fn from_bounds(bounds: impl RangeBounds<T>, len: T) -> Result<Self, SpanError> {
+        let start = match bounds.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(T::ONE).ok_or(SpanError::Overflow)?,
+            Bound::Unbounded => T::ZERO,
+        };
+
+        let end = match bounds.end_bound() {
+            Bound::Included(&end) => end.checked_add(T::ONE).ok_or(SpanError::Overflow)?,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+
+        if end > len {
+            let len = len.to_usize().ok_or(SpanError::Overflow)?;
+            return Err(SpanError::OutOfBounds { len });
+        }
+
+        Self::new(start, end)
+    }
+
+    /// Converts this span to another integer type, checking that both ends fit.
+    ///
+    /// Widening conversions are also available through `From`, and narrowing
+    /// ones through `TryFrom`.
+    /// ```rust
+    /// # use copyspan::{Span, SpanError};
+    /// let foo = Span::<u32>::from(10..300);
+    ///
+    /// assert_eq!(foo.try_cast::<u16>(), Ok(Span::from(10..300)));
+    /// assert_eq!(foo.try_cast::<u8>(), Err(SpanError::Overflow));
+    /// assert_eq!(Span::<u64>::from(foo), Span::from(10..300));
+    /// ```
+    pub fn try_cast<U: SpanIndex>(self) -> Result<Span<U>, SpanError> {
+        let start = U::from_i128(self.start.to_i128()).ok_or(SpanError::Overflow)?;
+        let end = U::from_i128(self.end.to_i128()).ok_or(SpanError::Overflow)?;
+
+        Ok(Span { start, end })
+    }
+
+    /// Sets the length of a span without changing its start. The end is
+    /// clamped to the largest value of `T`.
+    #[must_use]
+    pub fn saturating_with_len(self, len: T) -> Self {
+        Self {
+            start: self.start,
+            end: self.start.saturating_add(len),
+        }
+    }
+
+    /// Sets the length of a span without changing its start. The end wraps
+    /// around on overflow, so the result may end before it starts.
+    #[must_use]
+    pub fn wrapping_with_len(self, len: T) -> Self {
+        Self {
+            start: self.start,
+            end: self.start.wrapping_add(len),
+        }
+    }
+
+    /// The number of positions in this span, clamped to the range of `T`. Spans
+    /// that end before they start have a length of zero.
+    #[must_use]
+    pub fn saturating_len(&self) -> T {
+        if self.end < self.start {
+            T::ZERO
+        } else {
+            self.end.saturating_sub(self.start)
+        }
+    }
+
+    /// Shifts both ends of a span forward, or returns `None` if either end
+    /// would overflow
+    /// ```rust
+    /// # use copyspan::Span;
+    /// let foo = Span::<u8>::from(10..20);
+    ///
+    /// assert_eq!(foo.checked_shift(5), Some(Span::from(15..25)));
+    /// assert_eq!(foo.checked_shift(240), None);
+    /// assert_eq!(foo.checked_shift_back(11), None);
+    /// ```
+    #[must_use]
+    pub fn checked_shift(self, by: T) -> Option<Self> {
+        Some(Self {
+            start: self.start.checked_add(by)?,
+            end: self.end.checked_add(by)?,
+        })
+    }
+
+    /// Shifts both ends of a span backward, or returns `None` if either end
+    /// would overflow
+    #[must_use]
+    pub fn checked_shift_back(self, by: T) -> Option<Self> {
+        Some(Self {
+            start: self.start.checked_sub(by)?,
+            end: self.end.checked_sub(by)?,
+        })
+    }
+
+    /// Shifts both ends of a span forward, clamping each end to the range of
+    /// `T`. The span gets shorter if its end is clamped.
+    #[must_use]
+    pub fn saturating_shift(self, by: T) -> Self {
+        Self {
+            start: self.start.saturating_add(by),
+            end: self.end.saturating_add(by),
+        }
+    }
+
+    /// Shifts both ends of a span backward, clamping each end to the range of
+    /// `T`. The span gets shorter if its start is clamped.
+    #[must_use]
+    pub fn saturating_shift_back(self, by: T) -> Self {
+        Self {
+            start: self.start.saturating_sub(by),
+            end: self.end.saturating_sub(by),
+        }
+    }
+
+    /// Shifts both ends of a span forward, wrapping around on overflow
+    #[must_use]
+    pub fn wrapping_shift(self, by: T) -> Self {
+        Self {
+            start: self.start.wrapping_add(by),
+            end: self.end.wrapping_add(by),
+        }
+    }
+
+    /// Shifts both ends of a span backward, wrapping around on overflow
+    #[must_use]
+    pub fn wrapping_shift_back(self, by: T) -> Self {
+        Self {
+            start: self.start.wrapping_sub(by),
+            end: self.end.wrapping_sub(by),
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 mod alloc_impl {
-    use crate::util::RustNumber;
+    use crate::SpanPoint;
     use alloc::fmt::Debug;
 
     use super::Span;
 
-    impl<T: RustNumber> Debug for Span<T> {
+    impl<T: SpanPoint> Debug for Span<T> {
         fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
             Debug::fmt(&self.range(), f)
         }
     }
 }
 
-impl<T: RustNumber> Hash for Span<T> {
+/// Equivalent to [`Span::intersection`]
+impl<T: SpanPoint> BitAnd for Span<T> {
+    type Output = Option<Self>;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+/// Equivalent to [`Span::hull`]
+impl<T: SpanPoint> BitOr for Span<T> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.hull(rhs)
+    }
+}
+
+impl<T: SpanPoint> BitOrAssign for Span<T> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.hull(rhs);
+    }
+}
+
+/// Shifts both ends of a span forward
+impl<T: SpanIndex> Add<T> for Span<T> {
+    type Output = Self;
+
+    fn add(self, rhs: T) -> Self::Output {
+        Self {
+            start: self.start + rhs,
+            end: self.end + rhs,
+        }
+    }
+}
+
+impl<T: SpanIndex> AddAssign<T> for Span<T> {
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+/// Shifts both ends of a span backward
+impl<T: SpanIndex> Sub<T> for Span<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        Self {
+            start: self.start - rhs,
+            end: self.end - rhs,
+        }
+    }
+}
+
+impl<T: SpanIndex> SubAssign<T> for Span<T> {
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: SpanPoint> Hash for Span<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         Hash::hash(&self.range(), state)
     }
 }
 
-impl<T: RustNumber> PartialEq for Span<T> {
+impl<T: SpanPoint> PartialEq for Span<T> {
     fn eq(&self, other: &Self) -> bool {
         self.range().eq(&other.range())
     }
 }
 
-impl<T: RustNumber> Eq for Span<T> {}
+impl<T: SpanPoint> Eq for Span<T> {}
 
-impl<T: RustNumber> From<Range<T>> for Span<T> {
+impl<T: SpanPoint> From<Range<T>> for Span<T> {
     fn from(value: Range<T>) -> Self {
         Span {
             start: value.start,
@@ -201,41 +679,168 @@ impl<T: RustNumber> From<Range<T>> for Span<T> {
     }
 }
 
-impl<T: RustNumber> From<Span<T>> for Range<T> {
+impl<T: SpanPoint> From<Span<T>> for Range<T> {
     fn from(value: Span<T>) -> Self {
         value.range()
     }
 }
 
-impl<U> Index<Span<usize>> for [U] {
+/// Fallible indexing with a [`Span`] of any integer type.
+///
+/// Unlike indexing with `[]`, these methods return a [`SpanError`] instead of
+/// panicking. Strings can only be indexed by spans that count bytes, which are
+/// spans of a [`ByteIndex`].
+/// ```rust
+/// # use copyspan::{GetSpan, Span, SpanError};
+/// let text = "héllo";
+///
+/// assert_eq!(text.get_span(Span::from(3..6)), Ok("llo"));
+/// assert_eq!(text.get_span(Span::<u8>::from(3..6)), Ok("llo"));
+/// assert_eq!(text.get_span(Span::from(2..6)), Err(SpanError::NotCharBoundary { at: 2 }));
+/// assert_eq!(text.get_span(Span::from(3..9)), Err(SpanError::OutOfBounds { len: 6 }));
+/// assert_eq!(text.get_span(Span::from(4..3)), Err(SpanError::Inverted));
+/// assert_eq!(text.get_span(Span::from(-1..3)), Err(SpanError::Overflow));
+/// ```
+pub trait GetSpan<T: SpanIndex> {
+    fn get_span(&self, span: Span<T>) -> Result<&Self, SpanError>;
+    fn get_span_mut(&mut self, span: Span<T>) -> Result<&mut Self, SpanError>;
+}
+
+/// Converts a span to a `Span<usize>` and checks that it can be used to index
+/// something with length `len`
+fn check_bounds<T: SpanIndex>(span: Span<T>, len: usize) -> Result<Span<usize>, SpanError> {
+    let start = span.start.to_usize().ok_or(SpanError::Overflow)?;
+    let end = span.end.to_usize().ok_or(SpanError::Overflow)?;
+
+    if start > end {
+        Err(SpanError::Inverted)
+    } else if end > len {
+        Err(SpanError::OutOfBounds { len })
+    } else {
+        Ok(Span { start, end })
+    }
+}
+
+fn check_str_bounds<T: ByteIndex>(span: Span<T>, s: &str) -> Result<Span<usize>, SpanError> {
+    let span = check_bounds(span, s.len())?;
+
+    for at in [span.start, span.end] {
+        if !s.is_char_boundary(at) {
+            return Err(SpanError::NotCharBoundary { at });
+        }
+    }
+
+    Ok(span)
+}
+
+impl<U, T: SpanIndex> GetSpan<T> for [U] {
+    fn get_span(&self, span: Span<T>) -> Result<&Self, SpanError> {
+        let span = check_bounds(span, self.len())?;
+        Ok(&self[span.range()])
+    }
+
+    fn get_span_mut(&mut self, span: Span<T>) -> Result<&mut Self, SpanError> {
+        let span = check_bounds(span, self.len())?;
+        Ok(&mut self[span.range()])
+    }
+}
+
+impl<T: ByteIndex> GetSpan<T> for str {
+    fn get_span(&self, span: Span<T>) -> Result<&Self, SpanError> {
+        let span = check_str_bounds(span, self)?;
+        Ok(&self[span.range()])
+    }
+
+    fn get_span_mut(&mut self, span: Span<T>) -> Result<&mut Self, SpanError> {
+        let span = check_str_bounds(span, self)?;
+        Ok(&mut self[span.range()])
+    }
+}
+
+/// Indexes with a span of any integer type.
+///
+/// # Panics
+/// Panics if the span is out of bounds, starts after it ends, or has a
+/// position that doesn't fit in a `usize` (such as a negative one). Use
+/// [`GetSpan::get_span`] to handle these cases instead.
+/// ```rust
+/// # use copyspan::Span;
+/// let mut values = [1, 2, 3, 4, 5];
+///
+/// assert_eq!(values[Span::<u16>::from(1..3)], [2, 3]);
+/// assert_eq!(values[Span::<i64>::from(3..5)], [4, 5]);
+///
+/// values[Span::<u8>::from(0..2)].fill(0);
+/// assert_eq!(values, [0, 0, 3, 4, 5]);
+/// ```
+///
+/// ```rust,should_panic
+/// # use copyspan::Span;
+/// let values = [1, 2, 3];
+/// let _ = &values[Span::<i32>::from(-1..2)];
+/// ```
+impl<U, T: SpanIndex> Index<Span<T>> for [U] {
     type Output = [U];
 
-    fn index(&self, index: Span<usize>) -> &Self::Output {
-        &self[index.range()]
+    #[track_caller]
+    fn index(&self, index: Span<T>) -> &Self::Output {
+        self.get_span(index).unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
-impl<U> IndexMut<Span<usize>> for [U] {
-    fn index_mut(&mut self, index: Span<usize>) -> &mut Self::Output {
-        &mut self[index.range()]
+impl<U, T: SpanIndex> IndexMut<Span<T>> for [U] {
+    #[track_caller]
+    fn index_mut(&mut self, index: Span<T>) -> &mut Self::Output {
+        self.get_span_mut(index)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
-impl Index<Span<usize>> for str {
+/// Indexes with a span of bytes. Spans in other units, such as
+/// [`Utf16`], have to be converted first.
+///
+/// # Panics
+/// Panics in the same cases as indexing a slice, and also if the span doesn't
+/// start and end on char boundaries. The panic message is the
+/// [`SpanError`] that [`GetSpan::get_span`] would return.
+/// ```rust
+/// # use copyspan::Span;
+/// # use std::panic::catch_unwind;
+/// let text = "héllo";
+///
+/// assert_eq!(&text[Span::<u32>::from(3..6)], "llo");
+///
+/// let message = |span: Span<u32>| {
+///     let payload = catch_unwind(|| &text[span]).unwrap_err();
+///     *payload.downcast::<String>().unwrap()
+/// };
+///
+/// assert_eq!(message(Span::from(3..9)), "span is out of bounds for length 6");
+/// assert_eq!(message(Span::from(2..6)), "byte index 2 is not a char boundary");
+/// ```
+///
+/// ```rust,should_panic
+/// # use copyspan::Span;
+/// let _ = &"héllo"[Span::<u32>::from(1..2)];
+/// ```
+impl<T: ByteIndex> Index<Span<T>> for str {
     type Output = str;
 
-    fn index(&self, index: Span<usize>) -> &Self::Output {
-        &self[index.range()]
+    #[track_caller]
+    fn index(&self, index: Span<T>) -> &Self::Output {
+        self.get_span(index).unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
-impl IndexMut<Span<usize>> for str {
-    fn index_mut(&mut self, index: Span<usize>) -> &mut Self::Output {
-        &mut self[index.range()]
+impl<T: ByteIndex> IndexMut<Span<T>> for str {
+    #[track_caller]
+    fn index_mut(&mut self, index: Span<T>) -> &mut Self::Output {
+        self.get_span_mut(index)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
-impl<T: RustNumber> IntoIterator for Span<T>
+impl<T: SpanIndex> IntoIterator for Span<T>
 where
     Range<T>: Iterator<Item = T>,
 {
@@ -248,7 +853,7 @@ where
     }
 }
 
-impl<T: RustNumber> Default for Span<T> {
+impl<T: SpanIndex> Default for Span<T> {
     fn default() -> Self {
         Self::from(T::default()..T::default())
     }
@@ -0,0 +1,283 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+use crate::Span;
+
+/// A zero-based line and column
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+/// The unit that a column is counted in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColumnUnit {
+    /// UTF-8 bytes
+    #[default]
+    Utf8,
+    /// UTF-16 code units, as used by the Language Server Protocol
+    Utf16,
+    /// Unicode scalar values (`char`s)
+    Char,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineIndexError {
+    /// The offset is past the end of the text
+    OutOfBounds { offset: usize, len: usize },
+    /// The offset is inside of a multi-byte character
+    NotCharBoundary { offset: usize },
+    /// The line does not exist
+    LineOutOfBounds { line: usize, line_count: usize },
+    /// The column is past the end of its line
+    ColumnOutOfBounds(LineCol),
+    /// The column is between the two UTF-16 code units of a surrogate pair
+    ColumnInsideChar(LineCol),
+}
+
+impl Display for LineIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { offset, len } => {
+                write!(
+                    f,
+                    "offset {offset} is out of bounds for text of length {len}"
+                )
+            }
+            Self::NotCharBoundary { offset } => {
+                write!(f, "offset {offset} is not on a char boundary")
+            }
+            Self::LineOutOfBounds { line, line_count } => {
+                write!(
+                    f,
+                    "line {line} is out of bounds for text with {line_count} lines"
+                )
+            }
+            Self::ColumnOutOfBounds(lc) => {
+                write!(f, "column {} is past the end of line {}", lc.col, lc.line)
+            }
+            Self::ColumnInsideChar(lc) => {
+                write!(
+                    f,
+                    "column {} of line {} is inside of a char",
+                    lc.col, lc.line
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for LineIndexError {}
+
+/// A non-ASCII character in the source text
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct WideChar {
+    offset: usize,
+    len_utf8: u8,
+}
+
+impl WideChar {
+    fn end(self) -> usize {
+        self.offset + self.len_utf8 as usize
+    }
+
+    fn width(self, unit: ColumnUnit) -> usize {
+        match unit {
+            ColumnUnit::Utf8 => self.len_utf8 as usize,
+            ColumnUnit::Utf16 if self.len_utf8 == 4 => 2,
+            ColumnUnit::Utf16 | ColumnUnit::Char => 1,
+        }
+    }
+}
+
+/// Converts between byte offsets and line/column pairs in a piece of text.
+///
+/// Lines are separated by `'\n'`. Any `'\r'` before the `'\n'` is counted as
+/// part of the line. The text itself is not stored; only the line starts and
+/// the positions of non-ASCII characters are.
+/// ```rust
+/// # use copyspan::{Span, LineIndex};
+/// # use copyspan::line_index::{ColumnUnit, LineCol};
+/// let index = LineIndex::new("fn main() {\n    let ö = '𝄞';\n}");
+///
+/// let pos = index.line_col(31, ColumnUnit::Utf8).unwrap();
+/// assert_eq!(pos, LineCol { line: 1, col: 19 });
+///
+/// // `ö` is two bytes but one UTF-16 unit, and `𝄞` is four bytes but two UTF-16 units
+/// assert_eq!(index.line_col(31, ColumnUnit::Utf16).unwrap().col, 16);
+/// assert_eq!(index.line_col(31, ColumnUnit::Char).unwrap().col, 15);
+///
+/// assert_eq!(index.offset(LineCol { line: 1, col: 16 }, ColumnUnit::Utf16), Ok(31));
+/// assert!(index.line_col(21, ColumnUnit::Utf8).is_err()); // inside of `ö`
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LineIndex {
+    len: usize,
+    line_starts: Vec<usize>,
+    wide_chars: Vec<WideChar>,
+}
+
+impl LineIndex {
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = Vec::from([0]);
+        let mut wide_chars = Vec::new();
+
+        for (offset, c) in text.char_indices() {
+            if c == '\n' {
+                line_starts.push(offset + 1);
+            } else if !c.is_ascii() {
+                wide_chars.push(WideChar {
+                    offset,
+                    len_utf8: c.len_utf8() as u8,
+                });
+            }
+        }
+
+        Self {
+            len: text.len(),
+            line_starts,
+            wide_chars,
+        }
+    }
+
+    /// The length of the indexed text in bytes
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of lines in the text. This is always at least one.
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The span of a line, including its trailing `'\n'` if it has one
+    #[must_use]
+    pub fn line_span(&self, line: usize) -> Option<Span<usize>> {
+        let start = *self.line_starts.get(line)?;
+        let end = self.line_starts.get(line + 1).copied().unwrap_or(self.len);
+
+        Some(Span::from(start..end))
+    }
+
+    /// The line that contains a byte offset
+    pub fn line_of(&self, offset: usize) -> Result<usize, LineIndexError> {
+        if offset > self.len {
+            return Err(LineIndexError::OutOfBounds {
+                offset,
+                len: self.len,
+            });
+        }
+
+        Ok(self.line_starts.partition_point(|&s| s <= offset) - 1)
+    }
+
+    /// Converts a byte offset to a line and a column in `unit`s
+    pub fn line_col(&self, offset: usize, unit: ColumnUnit) -> Result<LineCol, LineIndexError> {
+        let line = self.line_of(offset)?;
+        let line_start = self.line_starts[line];
+
+        let idx = self.wide_chars.partition_point(|w| w.offset < offset);
+        if idx > 0 && self.wide_chars[idx - 1].end() > offset {
+            return Err(LineIndexError::NotCharBoundary { offset });
+        }
+
+        let first = self.wide_chars.partition_point(|w| w.offset < line_start);
+        let col = self.wide_chars[first..idx]
+            .iter()
+            .fold(offset - line_start, |col, w| {
+                col - w.len_utf8 as usize + w.width(unit)
+            });
+
+        Ok(LineCol { line, col })
+    }
+
+    /// Converts both ends of a span to lines and columns
+    pub fn span_line_cols(
+        &self,
+        span: Span<usize>,
+        unit: ColumnUnit,
+    ) -> Result<(LineCol, LineCol), LineIndexError> {
+        Ok((
+            self.line_col(span.start, unit)?,
+            self.line_col(span.end, unit)?,
+        ))
+    }
+
+    /// Converts a line and a column in `unit`s to a byte offset.
+    ///
+    /// The column may point at the end of the line, but not past it.
+    pub fn offset(&self, pos: LineCol, unit: ColumnUnit) -> Result<usize, LineIndexError> {
+        let line_start =
+            *self
+                .line_starts
+                .get(pos.line)
+                .ok_or(LineIndexError::LineOutOfBounds {
+                    line: pos.line,
+                    line_count: self.line_count(),
+                })?;
+
+        // The last valid offset on the line, which is either its `'\n'` or
+        // the end of the text
+        let line_end = self
+            .line_starts
+            .get(pos.line + 1)
+            .map_or(self.len, |next| next - 1);
+
+        let first = self.wide_chars.partition_point(|w| w.offset < line_start);
+        let last = self.wide_chars.partition_point(|w| w.offset < line_end);
+
+        let mut offset = line_start;
+        let mut remaining = pos.col;
+
+        for &w in &self.wide_chars[first..last] {
+            let narrow = w.offset - offset;
+            if remaining <= narrow {
+                break;
+            }
+
+            remaining -= narrow;
+            offset = w.offset;
+
+            let width = w.width(unit);
+            if remaining < width {
+                return Err(if unit == ColumnUnit::Utf8 {
+                    LineIndexError::NotCharBoundary {
+                        offset: offset + remaining,
+                    }
+                } else {
+                    LineIndexError::ColumnInsideChar(pos)
+                });
+            }
+
+            remaining -= width;
+            offset = w.end();
+        }
+
+        if remaining > line_end - offset {
+            return Err(LineIndexError::ColumnOutOfBounds(pos));
+        }
+
+        Ok(offset + remaining)
+    }
+
+    /// Converts a pair of lines and columns to a span of bytes
+    pub fn span(
+        &self,
+        start: LineCol,
+        end: LineCol,
+        unit: ColumnUnit,
+    ) -> Result<Span<usize>, LineIndexError> {
+        Ok(Span::from(
+            self.offset(start, unit)?..self.offset(end, unit)?,
+        ))
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{Span, SpanIndex};
+
+/// An `Option<Span<T>>` that is the same size as a `Span<T>`.
+///
+/// `None` is stored as the inverted span `T::MAX..T::MIN`, so that span is the
+/// one value that can't be stored as `Some`. Spans made with [`Span::new`] are
+/// never inverted, so they can always be stored.
+/// ```rust
+/// # use copyspan::{OptionSpan, Span};
+/// assert_eq!(size_of::<Option<Span<u32>>>(), 12);
+/// assert_eq!(size_of::<OptionSpan<u32>>(), 8);
+///
+/// let span = OptionSpan::from(Some(Span::from(2..5u32)));
+///
+/// assert_eq!(span.get(), Some(Span::from(2..5)));
+/// assert_eq!(span.map(|s| s.len()), Some(3));
+/// assert_eq!(OptionSpan::NONE.unwrap_or(Span::from(0..0u32)), Span::from(0..0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct OptionSpan<T: SpanIndex = usize> {
+    start: T,
+    end: T,
+}
+
+impl<T: SpanIndex> OptionSpan<T> {
+    pub const NONE: Self = Self {
+        start: T::MAX,
+        end: T::MIN,
+    };
+
+    /// Wraps a span.
+    ///
+    /// # Panics
+    /// Panics if `span` is `T::MAX..T::MIN`, which is used to store `None`.
+    #[must_use]
+    #[track_caller]
+    pub fn some(span: Span<T>) -> Self {
+        let this = Self {
+            start: span.start,
+            end: span.end,
+        };
+
+        assert!(this.is_some(), "span is reserved for `OptionSpan::NONE`");
+
+        this
+    }
+
+    #[must_use]
+    pub fn is_some(self) -> bool {
+        !self.is_none()
+    }
+
+    #[must_use]
+    pub fn is_none(self) -> bool {
+        self == Self::NONE
+    }
+
+    /// Gets the span, if there is one
+    #[must_use]
+    pub fn get(self) -> Option<Span<T>> {
+        self.is_some().then_some(Span {
+            start: self.start,
+            end: self.end,
+        })
+    }
+
+    /// Equivalent to [`Option::map`]
+    pub fn map<U>(self, f: impl FnOnce(Span<T>) -> U) -> Option<U> {
+        self.get().map(f)
+    }
+
+    /// Equivalent to [`Option::unwrap_or`]
+    #[must_use]
+    pub fn unwrap_or(self, default: Span<T>) -> Span<T> {
+        self.get().unwrap_or(default)
+    }
+
+    /// Equivalent to [`Option::unwrap_or_else`]
+    pub fn unwrap_or_else(self, f: impl FnOnce() -> Span<T>) -> Span<T> {
+        self.get().unwrap_or_else(f)
+    }
+
+    /// Gets the span.
+    ///
+    /// # Panics
+    /// Panics if this is `None`.
+    #[must_use]
+    #[track_caller]
+    pub fn unwrap(self) -> Span<T> {
+        self.get()
+            .expect("called `OptionSpan::unwrap()` on a `None` value")
+    }
+
+    /// Takes the span out, leaving `None` in its place
+    pub fn take(&mut self) -> Option<Span<T>> {
+        core::mem::replace(self, Self::NONE).get()
+    }
+}
+
+impl<T: SpanIndex> Default for OptionSpan<T> {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: SpanIndex> core::fmt::Debug for OptionSpan<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+/// # Panics
+/// Panics if the span is `T::MAX..T::MIN`. See [`OptionSpan::some`].
+impl<T: SpanIndex> From<Option<Span<T>>> for OptionSpan<T> {
+    fn from(value: Option<Span<T>>) -> Self {
+        value.map_or(Self::NONE, Self::some)
+    }
+}
+
+/// # Panics
+/// Panics if the span is `T::MAX..T::MIN`. See [`OptionSpan::some`].
+impl<T: SpanIndex> From<Span<T>> for OptionSpan<T> {
+    fn from(value: Span<T>) -> Self {
+        Self::some(value)
+    }
+}
+
+impl<T: SpanIndex> From<OptionSpan<T>> for Option<Span<T>> {
+    fn from(value: OptionSpan<T>) -> Self {
+        value.get()
+    }
+}
@@ -0,0 +1,149 @@
+use core::{cmp::Ordering, fmt::Debug};
+
+use crate::{Span, SpanId, SpanInterner};
+
+const START_SHIFT: u32 = 32;
+const LEN_MASK: u64 = 0xffff;
+/// The length field of spans that are stored in a [`SpanInterner`]
+const ESCAPE_TAG: u64 = LEN_MASK;
+const MAX_INLINE_LEN: u32 = (ESCAPE_TAG - 1) as u32;
+
+/// A `Span<u32>` packed into eight bytes.
+///
+/// The upper 32 bits hold the start of the span, and the lowest 16 bits hold
+/// its length, so every span shorter than `65535` is stored inline. Longer
+/// spans are interned in a [`SpanInterner<u32>`], and the packed span holds
+/// their [`SpanId`] instead of their start.
+///
+/// [`PackedSpan::new_in`] and [`PackedSpan::span_in`] take the interner
+/// explicitly, while [`PackedSpan::new`] and [`PackedSpan::span`] use the
+/// interner that is in scope through [`SpanInterner::scope`]. Packed spans
+/// from different interners can't be mixed.
+///
+/// Because every span has exactly one packed form in an interner, equality
+/// and hashing work directly on the packed bits. Ordering needs the interner,
+/// so it is done with [`PackedSpan::cmp_in`] instead of `Ord`.
+/// ```rust
+/// # use copyspan::{PackedSpan, Span, SpanInterner};
+/// let interner = SpanInterner::new();
+///
+/// let short = PackedSpan::new_in(Span::from(100..120), &interner);
+/// let long = PackedSpan::new_in(Span::from(100..100_000), &interner);
+///
+/// assert_eq!(size_of::<PackedSpan>(), 8);
+/// assert!(short.is_inline());
+/// assert!(!long.is_inline());
+/// assert_eq!(interner.len(), 1);
+///
+/// assert_eq!(long.span_in(&interner), Span::from(100..100_000));
+/// assert_eq!(long, PackedSpan::new_in(Span::from(100..100_000), &interner));
+///
+/// let mut spans = [long, short];
+/// spans.sort_by(|a, b| a.cmp_in(b, &interner));
+/// assert_eq!(spans, [short, long]);
+///
+/// interner.scope(|| assert_eq!(long.span(), Span::from(100..100_000)));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PackedSpan(u64);
+
+impl PackedSpan {
+    /// Packs a span, interning it in the interner that is in scope if it
+    /// doesn't fit inline.
+    ///
+    /// # Panics
+    /// Panics if the span doesn't fit inline and no `SpanInterner<u32>` is in
+    /// scope.
+    #[must_use]
+    pub fn new(span: Span<u32>) -> Self {
+        Self::try_inline(span).unwrap_or_else(|| {
+            SpanInterner::<u32>::with_current(|interner| Self::new_in(span, interner))
+        })
+    }
+
+    /// Packs a span, interning it in `interner` if it doesn't fit inline
+    #[must_use]
+    pub fn new_in(span: Span<u32>, interner: &SpanInterner<u32>) -> Self {
+        Self::try_inline(span).unwrap_or_else(|| {
+            let id = interner.intern(span);
+            Self(u64::from(id.0) << START_SHIFT | ESCAPE_TAG)
+        })
+    }
+
+    /// Packs a span without an interner, or returns `None` if it doesn't fit
+    /// inline
+    #[must_use]
+    pub fn try_inline(span: Span<u32>) -> Option<Self> {
+        let len = span.checked_len()?;
+
+        (len <= MAX_INLINE_LEN)
+            .then_some(Self(u64::from(span.start) << START_SHIFT | u64::from(len)))
+    }
+
+    /// Unpacks this span, looking it up in the interner that is in scope if
+    /// it isn't inline.
+    ///
+    /// # Panics
+    /// Panics if the span isn't inline and no `SpanInterner<u32>` is in scope.
+    #[must_use]
+    pub fn span(self) -> Span<u32> {
+        self.try_unpack()
+            .unwrap_or_else(|id| SpanInterner::<u32>::with_current(|interner| interner.span(id)))
+    }
+
+    /// Unpacks this span, looking it up in `interner` if it isn't inline
+    #[must_use]
+    pub fn span_in(self, interner: &SpanInterner<u32>) -> Span<u32> {
+        self.try_unpack().unwrap_or_else(|id| interner.span(id))
+    }
+
+    /// The inline span, or the id that it is interned as
+    fn try_unpack(self) -> Result<Span<u32>, SpanId> {
+        let start_or_id = (self.0 >> START_SHIFT) as u32;
+
+        if self.is_inline() {
+            Ok(Span::at(start_or_id).with_len((self.0 & LEN_MASK) as u32))
+        } else {
+            Err(SpanId(start_or_id))
+        }
+    }
+
+    /// Compares the unpacked spans by their start and then by their end,
+    /// looking up spans that aren't inline in `interner`
+    #[must_use]
+    pub fn cmp_in(&self, other: &Self, interner: &SpanInterner<u32>) -> Ordering {
+        // Inline spans order the same way as their bits do
+        if self.is_inline() && other.is_inline() {
+            return self.0.cmp(&other.0);
+        }
+
+        let (a, b) = (self.span_in(interner), other.span_in(interner));
+        (a.start, a.end).cmp(&(b.start, b.end))
+    }
+
+    /// Whether this span is stored without an interner
+    #[must_use]
+    pub const fn is_inline(self) -> bool {
+        self.0 & LEN_MASK != ESCAPE_TAG
+    }
+
+    /// The raw packed representation
+    #[must_use]
+    pub const fn to_bits(self) -> u64 {
+        self.0
+    }
+}
+
+/// Spans that aren't inline are shown by their id if no interner is in scope
+impl Debug for PackedSpan {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.try_unpack() {
+            Ok(span) => Debug::fmt(&span, f),
+            Err(id) => match SpanInterner::<u32>::try_with_current(|interner| interner.span(id)) {
+                Some(span) => Debug::fmt(&span, f),
+                None => Debug::fmt(&id, f),
+            },
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Support for casting spans to and from bytes. The `zerocopy` traits are
+//! derived on [`Span`] itself.
+
+#[cfg(feature = "bytemuck")]
+use bytemuck::{Pod, Zeroable};
+
+use crate::Span;
+#[cfg(feature = "bytemuck")]
+use crate::SpanIndex;
+
+/// Checks that `Span<T>` has no padding for every built-in `T`
+macro_rules! assert_no_padding {
+    ($($ty:ty),*) => {
+        $(const _: () = assert!(size_of::<Span<$ty>>() == 2 * size_of::<$ty>());)*
+    };
+}
+
+assert_no_padding!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+// SAFETY: `Span` is `repr(C)` with two fields of the same type, so it has no
+// padding, and any bit pattern that is valid for `T` is valid for both fields.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: SpanIndex + Zeroable> Zeroable for Span<T> {}
+
+/// ```rust
+/// # use copyspan::Span;
+/// let bytes: [u8; 16] = bytemuck::cast([3u32, 7, 10, 12]);
+///
+/// let spans: &[Span<u32>] = bytemuck::cast_slice(&bytes);
+/// assert_eq!(spans, [Span::from(3..7), Span::from(10..12)]);
+/// ```
+// SAFETY: see above. `Span` is `Copy` whenever `T` is, and `T: Pod` implies
+// `T: 'static`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: SpanIndex + Pod> Pod for Span<T> {}
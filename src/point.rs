@@ -0,0 +1,153 @@
+use core::{fmt::Debug, hash::Hash};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::SpanIndex;
+
+/// A type that can be used as the start and end of a [`Span`](crate::Span)
+/// where the distance between two points may be a different type, such as
+/// `Instant` and `Duration`.
+///
+/// Every [`SpanIndex`] is a `SpanPoint` that is its own distance. This is also
+/// implemented for `*const u8` and `*mut u8`, and with the `std` feature for
+/// `Instant` and `SystemTime`. That gives them the methods of
+/// [`Span`](crate::Span) that only compare points or measure lengths.
+/// ```rust
+/// # use copyspan::Span;
+/// let bytes = [0u8; 16];
+/// let region = Span::from(bytes.as_ptr_range());
+///
+/// assert_eq!(region.len(), 16);
+/// assert!(region.contains(&bytes[3] as *const u8));
+/// ```
+pub trait SpanPoint: Copy + Ord + Hash + Debug {
+    type Distance: Copy;
+
+    /// The point `distance` after this one. This overflows the same way that
+    /// `+` does on `Self`.
+    fn add_distance(self, distance: Self::Distance) -> Self;
+
+    /// The point `distance` after this one, or `None` if it can't be
+    /// represented or `distance` is negative
+    fn checked_add_distance(self, distance: Self::Distance) -> Option<Self>;
+
+    /// The distance from this point to a later point. This overflows the same
+    /// way that `-` does on `Self` if `end` is before this point.
+    fn distance_to(self, end: Self) -> Self::Distance;
+
+    /// The distance from this point to a later point, or `None` if `end` is
+    /// before this point
+    fn checked_distance_to(self, end: Self) -> Option<Self::Distance>;
+}
+
+impl<T: SpanIndex> SpanPoint for T {
+    type Distance = T;
+
+    fn add_distance(self, distance: T) -> T {
+        self + distance
+    }
+
+    fn checked_add_distance(self, distance: T) -> Option<T> {
+        if distance < T::ZERO {
+            return None;
+        }
+
+        self.checked_add(distance)
+    }
+
+    fn distance_to(self, end: T) -> T {
+        end - self
+    }
+
+    fn checked_distance_to(self, end: T) -> Option<T> {
+        if end < self {
+            return None;
+        }
+
+        end.checked_sub(self)
+    }
+}
+
+/// Distances from a later instant to an earlier one are zero, like with `-`.
+/// ```rust
+/// # use copyspan::Span;
+/// # use std::time::{Duration, Instant};
+/// let now = Instant::now();
+/// let window = Span::from_start_len(now, Duration::from_secs(5)).unwrap();
+///
+/// assert!(window.contains(now));
+/// assert!(!window.contains(now + Duration::from_secs(5)));
+/// assert_eq!(window.len(), Duration::from_secs(5));
+/// ```
+#[cfg(feature = "std")]
+impl SpanPoint for Instant {
+    type Distance = Duration;
+
+    fn add_distance(self, distance: Duration) -> Instant {
+        self + distance
+    }
+
+    fn checked_add_distance(self, distance: Duration) -> Option<Instant> {
+        self.checked_add(distance)
+    }
+
+    fn distance_to(self, end: Instant) -> Duration {
+        end.saturating_duration_since(self)
+    }
+
+    fn checked_distance_to(self, end: Instant) -> Option<Duration> {
+        end.checked_duration_since(self)
+    }
+}
+
+/// Distances from a later time to an earlier one are zero
+#[cfg(feature = "std")]
+impl SpanPoint for SystemTime {
+    type Distance = Duration;
+
+    fn add_distance(self, distance: Duration) -> SystemTime {
+        self + distance
+    }
+
+    fn checked_add_distance(self, distance: Duration) -> Option<SystemTime> {
+        self.checked_add(distance)
+    }
+
+    fn distance_to(self, end: SystemTime) -> Duration {
+        end.duration_since(self).unwrap_or_default()
+    }
+
+    fn checked_distance_to(self, end: SystemTime) -> Option<Duration> {
+        end.duration_since(self).ok()
+    }
+}
+
+macro_rules! impl_pointer {
+    ($ty:ty) => {
+        /// Distances are measured in bytes. Adding a distance never
+        /// dereferences the pointer, so it is always safe.
+        impl SpanPoint for $ty {
+            type Distance = usize;
+
+            fn add_distance(self, distance: usize) -> $ty {
+                self.wrapping_byte_add(distance)
+            }
+
+            fn checked_add_distance(self, distance: usize) -> Option<$ty> {
+                self.addr().checked_add(distance)?;
+                Some(self.wrapping_byte_add(distance))
+            }
+
+            fn distance_to(self, end: $ty) -> usize {
+                end.addr() - self.addr()
+            }
+
+            fn checked_distance_to(self, end: $ty) -> Option<usize> {
+                end.addr().checked_sub(self.addr())
+            }
+        }
+    };
+}
+
+impl_pointer!(*const u8);
+impl_pointer!(*mut u8);
@@ -0,0 +1,332 @@
+use alloc::vec::Vec;
+use core::{fmt::Debug, iter::FusedIterator, slice};
+
+use crate::{Span, SpanIndex};
+
+/// A map from non-overlapping [`Span`]s to values.
+///
+/// Inserting a span overwrites the parts of any existing entries that it
+/// overlaps with, splitting them if needed. Touching entries with equal values
+/// are merged together, and empty and inverted spans are never stored.
+/// ```rust
+/// # use copyspan::{Span, RangeMap};
+/// let mut styles = RangeMap::new();
+/// styles.insert(Span::from(0..10), "plain");
+/// styles.insert(Span::from(3..5), "bold");
+///
+/// assert_eq!(styles.get(4), Some(&"bold"));
+/// assert_eq!(styles.get(7), Some(&"plain"));
+/// assert_eq!(styles.len(), 3);
+///
+/// styles.insert(Span::from(3..5), "plain");
+/// assert_eq!(styles.iter().collect::<Vec<_>>(), [(Span::from(0..10), &"plain")]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RangeMap<T: SpanIndex, V> {
+    entries: Vec<(Span<T>, V)>,
+}
+
+impl<T: SpanIndex, V> RangeMap<T, V> {
+    /// Creates an empty map
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// The number of disjoint entries in this map
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Gets the value at a position
+    #[must_use]
+    pub fn get(&self, pos: T) -> Option<&V> {
+        self.get_entry(pos).map(|(_, v)| v)
+    }
+
+    /// Gets the entry containing a position along with its full span
+    #[must_use]
+    pub fn get_entry(&self, pos: T) -> Option<(Span<T>, &V)> {
+        let idx = self.entries.partition_point(|(s, _)| s.end <= pos);
+
+        self.entries
+            .get(idx)
+            .filter(|(s, _)| s.start <= pos)
+            .map(|(s, v)| (*s, v))
+    }
+
+    /// Iterates over every entry in ascending order
+    pub fn iter(&self) -> Iter<'_, T, V> {
+        Iter {
+            inner: self.entries.iter(),
+            clip: None,
+        }
+    }
+
+    /// Iterates over the entries that overlap with `span`, clipped to `span`.
+    /// Empty and inverted spans yield nothing.
+    /// ```rust
+    /// # use copyspan::{Span, RangeMap};
+    /// let map = RangeMap::from_iter([(Span::from(0..4), 'a'), (Span::from(6..9), 'b')]);
+    ///
+    /// assert_eq!(
+    ///     map.range(Span::from(2..7)).collect::<Vec<_>>(),
+    ///     [(Span::from(2..4), &'a'), (Span::from(6..7), &'b')],
+    /// );
+    /// ```
+    pub fn range(&self, span: Span<T>) -> Iter<'_, T, V> {
+        let (lo, hi) = self.overlapping_indices(span);
+
+        Iter {
+            inner: self.entries[lo..hi].iter(),
+            clip: Some(span),
+        }
+    }
+
+    /// Iterates over the parts of `span` that are not covered by any entry
+    /// ```rust
+    /// # use copyspan::{Span, RangeMap};
+    /// let map = RangeMap::from_iter([(Span::from(2..4), ()), (Span::from(6..9), ())]);
+    ///
+    /// assert_eq!(
+    ///     map.gaps(Span::from(0..8)).collect::<Vec<_>>(),
+    ///     [Span::from(0..2), Span::from(4..6)],
+    /// );
+    /// ```
+    pub fn gaps(&self, span: Span<T>) -> Gaps<'_, T, V> {
+        let (lo, hi) = self.overlapping_indices(span);
+
+        Gaps {
+            inner: self.entries[lo..hi].iter(),
+            cursor: span.start,
+            end: span.end,
+        }
+    }
+
+    /// The indices of the entries that overlap with a non-empty span
+    fn overlapping_indices(&self, span: Span<T>) -> (usize, usize) {
+        if span.start >= span.end {
+            return (0, 0);
+        }
+
+        let lo = self.entries.partition_point(|(s, _)| s.end <= span.start);
+        let hi = self.entries.partition_point(|(s, _)| s.start < span.end);
+
+        (lo, hi.max(lo))
+    }
+}
+
+impl<T: SpanIndex, V: Clone> RangeMap<T, V> {
+    /// Removes every position in `span` from this map, splitting entries that
+    /// are only partially covered
+    pub fn remove(&mut self, span: Span<T>) {
+        let (lo, hi) = self.overlapping_indices(span);
+
+        if lo == hi {
+            return;
+        }
+
+        let (first, first_val) = &self.entries[lo];
+        let (last, last_val) = &self.entries[hi - 1];
+
+        let left =
+            (first.start < span.start).then(|| (first.with_end(span.start), first_val.clone()));
+        let right = (span.end < last.end).then(|| (last.with_start(span.end), last_val.clone()));
+
+        self.entries.splice(lo..hi, left.into_iter().chain(right));
+    }
+}
+
+impl<T: SpanIndex, V: Clone + PartialEq> RangeMap<T, V> {
+    /// Sets the value of every position in `span`. Empty and inverted spans
+    /// are ignored.
+    /// ```rust
+    /// # use copyspan::{Span, RangeMap};
+    /// let mut map = RangeMap::from_iter([(Span::from(0..3), 'a'), (Span::from(6..9), 'b')]);
+    /// map.insert(Span::from(8..2), 'c');
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.range(Span::from(8..2)).count(), 0);
+    /// ```
+    pub fn insert(&mut self, span: Span<T>, value: V) {
+        if span.start >= span.end {
+            return;
+        }
+
+        // Touching entries are included so that they can be merged
+        let lo = self.entries.partition_point(|(s, _)| s.end < span.start);
+        let hi = self.entries.partition_point(|(s, _)| s.start <= span.end);
+
+        let mut merged = span;
+        let mut left = None;
+        let mut right = None;
+
+        if lo < hi {
+            let (first, first_val) = &self.entries[lo];
+            let (last, last_val) = &self.entries[hi - 1];
+
+            if first.start < span.start {
+                if *first_val == value {
+                    merged.start = first.start;
+                } else {
+                    left = Some((first.with_end(first.end.min(span.start)), first_val.clone()));
+                }
+            }
+
+            if span.end < last.end {
+                if *last_val == value {
+                    merged.end = last.end;
+                } else {
+                    right = Some((last.with_start(last.start.max(span.end)), last_val.clone()));
+                }
+            }
+        }
+
+        self.entries.splice(
+            lo..hi,
+            left.into_iter().chain([(merged, value)]).chain(right),
+        );
+    }
+}
+
+impl<T: SpanIndex, V> Default for RangeMap<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SpanIndex, V: Debug> Debug for RangeMap<T, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T: SpanIndex, V: Clone + PartialEq> Extend<(Span<T>, V)> for RangeMap<T, V> {
+    fn extend<I: IntoIterator<Item = (Span<T>, V)>>(&mut self, iter: I) {
+        for (span, value) in iter {
+            self.insert(span, value);
+        }
+    }
+}
+
+impl<T: SpanIndex, V: Clone + PartialEq> FromIterator<(Span<T>, V)> for RangeMap<T, V> {
+    fn from_iter<I: IntoIterator<Item = (Span<T>, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, T: SpanIndex, V> IntoIterator for &'a RangeMap<T, V> {
+    type Item = (Span<T>, &'a V);
+
+    type IntoIter = Iter<'a, T, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`RangeMap`], optionally clipped to a
+/// span
+pub struct Iter<'a, T: SpanIndex, V> {
+    inner: slice::Iter<'a, (Span<T>, V)>,
+    clip: Option<Span<T>>,
+}
+
+impl<T: SpanIndex, V> Iter<'_, T, V> {
+    fn clip(&self, span: Span<T>) -> Span<T> {
+        match self.clip {
+            Some(clip) => Span::from(span.start.max(clip.start)..span.end.min(clip.end)),
+            None => span,
+        }
+    }
+}
+
+impl<T: SpanIndex, V> Clone for Iter<'_, T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            clip: self.clip,
+        }
+    }
+}
+
+impl<'a, T: SpanIndex, V> Iterator for Iter<'a, T, V> {
+    type Item = (Span<T>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (span, value) = self.inner.next()?;
+
+        Some((self.clip(*span), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: SpanIndex, V> DoubleEndedIterator for Iter<'_, T, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (span, value) = self.inner.next_back()?;
+
+        Some((self.clip(*span), value))
+    }
+}
+
+impl<T: SpanIndex, V> ExactSizeIterator for Iter<'_, T, V> {}
+impl<T: SpanIndex, V> FusedIterator for Iter<'_, T, V> {}
+
+/// An iterator over the uncovered parts of a span in a [`RangeMap`]
+pub struct Gaps<'a, T: SpanIndex, V> {
+    inner: slice::Iter<'a, (Span<T>, V)>,
+    cursor: T,
+    end: T,
+}
+
+impl<T: SpanIndex, V> Clone for Gaps<'_, T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            cursor: self.cursor,
+            end: self.end,
+        }
+    }
+}
+
+impl<T: SpanIndex, V> Iterator for Gaps<'_, T, V> {
+    type Item = Span<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cursor < self.end {
+            let start = self.cursor;
+
+            let Some((span, _)) = self.inner.next() else {
+                self.cursor = self.end;
+                return Some(Span::from(start..self.end));
+            };
+
+            self.cursor = span.end;
+
+            if start < span.start {
+                return Some(Span::from(start..span.start));
+            }
+        }
+
+        None
+    }
+}
+
+impl<T: SpanIndex, V> FusedIterator for Gaps<'_, T, V> {}
@@ -0,0 +1,138 @@
+use core::cmp::Ordering;
+
+use crate::{Span, SpanPoint};
+
+/// How one span relates to another, using Allen's interval algebra.
+///
+/// Each variant describes `a` in `a.relation(b)`. Exactly one relation holds
+/// for any two spans.
+///
+/// A zero-width span at `p` is treated as an infinitely small span that starts
+/// at `p`. This means that:
+/// - a zero-width span at the start of a span [`Starts`](Self::Starts) it
+/// - a zero-width span at the end of a span is [`MetBy`](Self::MetBy) it
+/// - a zero-width span never [`Meets`](Self::Meets) or
+///   [`Finishes`](Self::Finishes) anything
+/// - two zero-width spans at the same position are [`Equals`](Self::Equals)
+///
+/// With the exception of two identical zero-width spans, the relations where
+/// [`is_overlap`](Self::is_overlap) is true are exactly the ones where
+/// [`Span::overlaps_with`] is true.
+/// ```rust
+/// # use copyspan::{IntervalRelation, Span};
+/// let parent = Span::from(0..10);
+///
+/// assert_eq!(Span::from(2..5).relation(parent), IntervalRelation::During);
+/// assert_eq!(Span::from(0..5).relation(parent), IntervalRelation::Starts);
+/// assert_eq!(Span::from(8..12).relation(parent), IntervalRelation::OverlappedBy);
+/// assert_eq!(Span::from(10..12).relation(parent), IntervalRelation::MetBy);
+///
+/// assert_eq!(Span::at(0).relation(parent), IntervalRelation::Starts);
+/// assert_eq!(Span::at(10).relation(parent), IntervalRelation::MetBy);
+/// assert_eq!(parent.relation(Span::at(10)), IntervalRelation::Meets);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IntervalRelation {
+    /// `a` ends before `b` starts
+    Before,
+    /// `a` ends exactly where `b` starts
+    Meets,
+    /// `a` starts first and ends inside of `b`
+    Overlaps,
+    /// `a` and `b` start together and `a` ends first
+    Starts,
+    /// `a` is strictly inside of `b`
+    During,
+    /// `a` and `b` end together and `a` starts last
+    Finishes,
+    /// `a` and `b` are identical
+    Equals,
+    /// `b` and `a` end together and `b` starts last
+    FinishedBy,
+    /// `b` is strictly inside of `a`
+    Contains,
+    /// `b` and `a` start together and `b` ends first
+    StartedBy,
+    /// `b` starts first and ends inside of `a`
+    OverlappedBy,
+    /// `b` ends exactly where `a` starts
+    MetBy,
+    /// `b` ends before `a` starts
+    After,
+}
+
+impl IntervalRelation {
+    /// The relation of `b` to `a` given the relation of `a` to `b`
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::Before => Self::After,
+            Self::Meets => Self::MetBy,
+            Self::Overlaps => Self::OverlappedBy,
+            Self::Starts => Self::StartedBy,
+            Self::During => Self::Contains,
+            Self::Finishes => Self::FinishedBy,
+            Self::Equals => Self::Equals,
+            Self::FinishedBy => Self::Finishes,
+            Self::Contains => Self::During,
+            Self::StartedBy => Self::Starts,
+            Self::OverlappedBy => Self::Overlaps,
+            Self::MetBy => Self::Meets,
+            Self::After => Self::Before,
+        }
+    }
+
+    /// Whether `a` and `b` share any positions
+    #[must_use]
+    pub const fn is_overlap(self) -> bool {
+        !matches!(self, Self::Before | Self::Meets | Self::MetBy | Self::After)
+    }
+
+    /// Whether every position in `a` is also in `b`
+    #[must_use]
+    pub const fn is_inside(self) -> bool {
+        matches!(
+            self,
+            Self::Starts | Self::During | Self::Finishes | Self::Equals
+        )
+    }
+}
+
+/// An endpoint of a span. The end of a zero-width span is placed just after
+/// its start.
+type Endpoint<T> = (T, bool);
+
+fn endpoints<T: SpanPoint>(span: Span<T>) -> (Endpoint<T>, Endpoint<T>) {
+    ((span.start, false), (span.end, span.is_empty()))
+}
+
+pub(crate) fn relation<T: SpanPoint>(a: Span<T>, b: Span<T>) -> IntervalRelation {
+    use IntervalRelation::*;
+
+    let (a_start, a_end) = endpoints(a);
+    let (b_start, b_end) = endpoints(b);
+
+    match a_end.cmp(&b_start) {
+        Ordering::Less => return Before,
+        Ordering::Equal => return Meets,
+        Ordering::Greater => {}
+    }
+
+    match b_end.cmp(&a_start) {
+        Ordering::Less => return After,
+        Ordering::Equal => return MetBy,
+        Ordering::Greater => {}
+    }
+
+    match (a_start.cmp(&b_start), a_end.cmp(&b_end)) {
+        (Ordering::Equal, Ordering::Equal) => Equals,
+        (Ordering::Equal, Ordering::Less) => Starts,
+        (Ordering::Equal, Ordering::Greater) => StartedBy,
+        (Ordering::Greater, Ordering::Equal) => Finishes,
+        (Ordering::Less, Ordering::Equal) => FinishedBy,
+        (Ordering::Greater, Ordering::Less) => During,
+        (Ordering::Less, Ordering::Greater) => Contains,
+        (Ordering::Less, Ordering::Less) => Overlaps,
+        (Ordering::Greater, Ordering::Greater) => OverlappedBy,
+    }
+}
@@ -0,0 +1,200 @@
+//! [`serde`] support for [`Span`].
+//!
+//! Spans are written as a `{ "start": .., "end": .. }` map in human-readable
+//! formats such as JSON, and as a `(start, end)` tuple in other formats.
+//! Deserializing an inverted span fails unless the field uses [`unchecked`].
+//! ```rust
+//! # use copyspan::Span;
+//! let json = serde_json::to_string(&Span::from(3..7u32)).unwrap();
+//! assert_eq!(json, r#"{"start":3,"end":7}"#);
+//!
+//! let span: Span<u32> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(span, Span::from(3..7));
+//!
+//! assert!(serde_json::from_str::<Span<u32>>(r#"{"start":7,"end":3}"#).is_err());
+//! ```
+
+use core::{fmt, marker::PhantomData};
+
+use ::serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeStruct, SerializeTuple},
+};
+
+use crate::{Span, SpanError, SpanIndex};
+
+const FIELDS: &[&str] = &["start", "end"];
+
+impl<T: SpanIndex + Serialize> Serialize for Span<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let mut s = serializer.serialize_struct("Span", 2)?;
+            s.serialize_field("start", &self.start)?;
+            s.serialize_field("end", &self.end)?;
+            s.end()
+        } else {
+            let mut s = serializer.serialize_tuple(2)?;
+            s.serialize_element(&self.start)?;
+            s.serialize_element(&self.end)?;
+            s.end()
+        }
+    }
+}
+
+impl<'de, T: SpanIndex + Deserialize<'de>> Deserialize<'de> for Span<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer, true)
+    }
+}
+
+/// Serializes and deserializes spans without checking that `start <= end`.
+///
+/// Use this with `#[serde(with = "copyspan::serde::unchecked")]`.
+/// ```rust
+/// # use copyspan::Span;
+/// #[derive(serde::Deserialize)]
+/// struct Token {
+///     #[serde(with = "copyspan::serde::unchecked")]
+///     span: Span<u32>,
+/// }
+///
+/// let token: Token = serde_json::from_str(r#"{"span":{"start":7,"end":3}}"#).unwrap();
+/// assert_eq!(token.span, Span::from(7..3));
+/// ```
+pub mod unchecked {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Span, SpanIndex};
+
+    pub fn serialize<T, S>(span: &Span<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: SpanIndex + Serialize,
+        S: Serializer,
+    {
+        span.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Span<T>, D::Error>
+    where
+        T: SpanIndex + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        super::deserialize(deserializer, false)
+    }
+}
+
+fn deserialize<'de, T, D>(deserializer: D, validate: bool) -> Result<Span<T>, D::Error>
+where
+    T: SpanIndex + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let visitor = SpanVisitor {
+        validate,
+        marker: PhantomData,
+    };
+
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_struct("Span", FIELDS, visitor)
+    } else {
+        deserializer.deserialize_tuple(2, visitor)
+    }
+}
+
+struct SpanVisitor<T> {
+    validate: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T: SpanIndex> SpanVisitor<T> {
+    fn finish<E: de::Error>(&self, start: T, end: T) -> Result<Span<T>, E> {
+        if self.validate && start > end {
+            return Err(E::custom(SpanError::Inverted));
+        }
+
+        Ok(Span { start, end })
+    }
+}
+
+impl<'de, T: SpanIndex + Deserialize<'de>> Visitor<'de> for SpanVisitor<T> {
+    type Value = Span<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a span with a start and an end")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let start = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let end = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        self.finish(start, end)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut start = None;
+        let mut end = None;
+
+        while let Some(key) = map.next_key()? {
+            let slot = match key {
+                Field::Start => &mut start,
+                Field::End => &mut end,
+            };
+
+            if slot.is_some() {
+                return Err(de::Error::duplicate_field(FIELDS[key as usize]));
+            }
+
+            *slot = Some(map.next_value()?);
+        }
+
+        let start = start.ok_or_else(|| de::Error::missing_field("start"))?;
+        let end = end.ok_or_else(|| de::Error::missing_field("end"))?;
+
+        self.finish(start, end)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Start,
+    End,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct FieldVisitor;
+
+impl Visitor<'_> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("`start` or `end`")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        match value {
+            "start" => Ok(Field::Start),
+            "end" => Ok(Field::End),
+            _ => Err(de::Error::unknown_field(value, FIELDS)),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        match value {
+            0 => Ok(Field::Start),
+            1 => Ok(Field::End),
+            _ => Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(value),
+                &self,
+            )),
+        }
+    }
+}
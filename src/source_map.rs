@@ -0,0 +1,252 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display};
+
+use crate::{LineIndex, Span};
+
+/// Identifies a file in a [`SourceMap`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(u32);
+
+impl FileId {
+    /// The position of this file in the order that files were added
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A file that has been added to a [`SourceMap`]
+#[derive(Clone, Debug)]
+pub struct SourceFile {
+    name: String,
+    src: String,
+    start: u32,
+    lines: LineIndex,
+}
+
+impl SourceFile {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn src(&self) -> &str {
+        &self.src
+    }
+
+    /// The global span that this file occupies
+    #[must_use]
+    pub fn span(&self) -> Span<u32> {
+        Span::at(self.start).with_len(self.src.len() as u32)
+    }
+
+    #[must_use]
+    pub fn line_index(&self) -> &LineIndex {
+        &self.lines
+    }
+}
+
+/// A global span resolved to a location in one file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ResolvedSpan<'a> {
+    pub file: FileId,
+    /// The span relative to the start of the file
+    pub span: Span<usize>,
+    pub snippet: &'a str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SourceMapError {
+    /// The global offset space does not have room for another file
+    OutOfSpace,
+    /// The span's start is after its end
+    Inverted,
+    /// The position is not inside of any file
+    NoFile { pos: u32 },
+    /// The span starts and ends in different files
+    CrossesFiles { start: FileId, end: FileId },
+    /// The local span extends past the end of its file
+    OutOfBounds { len: usize },
+    /// The position is inside of a multi-byte character
+    NotCharBoundary { pos: u32 },
+}
+
+impl Display for SourceMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfSpace => write!(f, "source map is out of space"),
+            Self::Inverted => write!(f, "span starts after it ends"),
+            Self::NoFile { pos } => write!(f, "position {pos} is not inside of a file"),
+            Self::CrossesFiles { start, end } => write!(
+                f,
+                "span starts in file {} but ends in file {}",
+                start.0, end.0
+            ),
+            Self::OutOfBounds { len } => {
+                write!(f, "span is out of bounds for file of length {len}")
+            }
+            Self::NotCharBoundary { pos } => {
+                write!(f, "position {pos} is not on a char boundary")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SourceMapError {}
+
+/// A collection of files that share one global offset space, so that a single
+/// `Span<u32>` can identify a location in any of them.
+///
+/// Each file occupies a contiguous region of the offset space. The next file
+/// starts one position after the end of the previous one so that a zero-width
+/// span at the end of a file is never confused with one at the start of the
+/// next file.
+/// ```rust
+/// # use copyspan::{Span, SourceMap};
+/// let mut map = SourceMap::new();
+/// let main = map.add_file("main.rs", "mod util;").unwrap();
+/// let util = map.add_file("util.rs", "pub fn foo() {}").unwrap();
+///
+/// let global = map.global_span(util, Span::from(7..10)).unwrap();
+/// let resolved = map.resolve(global).unwrap();
+///
+/// assert_eq!(resolved.file, util);
+/// assert_eq!(resolved.span, Span::from(7..10));
+/// assert_eq!(resolved.snippet, "foo");
+///
+/// let whole = map.file(main).span().with_end(global.end);
+/// assert!(map.resolve(whole).is_err());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { files: Vec::new() }
+    }
+
+    /// Adds a file to the end of the offset space
+    pub fn add_file(
+        &mut self,
+        name: impl Into<String>,
+        src: impl Into<String>,
+    ) -> Result<FileId, SourceMapError> {
+        let src = src.into();
+
+        let start = match self.files.last() {
+            Some(last) => last
+                .span()
+                .end
+                .checked_add(1)
+                .ok_or(SourceMapError::OutOfSpace)?,
+            None => 0,
+        };
+
+        let len = u32::try_from(src.len()).map_err(|_| SourceMapError::OutOfSpace)?;
+        start.checked_add(len).ok_or(SourceMapError::OutOfSpace)?;
+
+        let id = u32::try_from(self.files.len()).map_err(|_| SourceMapError::OutOfSpace)?;
+
+        self.files.push(SourceFile {
+            name: name.into(),
+            lines: LineIndex::new(&src),
+            src,
+            start,
+        });
+
+        Ok(FileId(id))
+    }
+
+    /// Gets a file by its id.
+    ///
+    /// # Panics
+    /// Panics if `id` was created by a different `SourceMap`.
+    #[must_use]
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        &self.files[id.index()]
+    }
+
+    /// Iterates over every file in the order they were added
+    pub fn files(&self) -> impl ExactSizeIterator<Item = (FileId, &SourceFile)> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| (FileId(i as u32), file))
+    }
+
+    /// Finds the file containing a global position. The position just past the
+    /// end of a file is considered to be inside of it.
+    #[must_use]
+    pub fn file_at(&self, pos: u32) -> Option<FileId> {
+        let idx = self
+            .files
+            .partition_point(|f| f.start <= pos)
+            .checked_sub(1)?;
+
+        (pos <= self.files[idx].span().end).then_some(FileId(idx as u32))
+    }
+
+    /// Resolves a global span to a span inside of one file
+    pub fn resolve(&self, span: Span<u32>) -> Result<ResolvedSpan<'_>, SourceMapError> {
+        if span.start > span.end {
+            return Err(SourceMapError::Inverted);
+        }
+
+        let file = self
+            .file_at(span.start)
+            .ok_or(SourceMapError::NoFile { pos: span.start })?;
+        let end_file = self
+            .file_at(span.end)
+            .ok_or(SourceMapError::NoFile { pos: span.end })?;
+
+        if file != end_file {
+            return Err(SourceMapError::CrossesFiles {
+                start: file,
+                end: end_file,
+            });
+        }
+
+        let source = self.file(file);
+        let local =
+            Span::from((span.start - source.start) as usize..(span.end - source.start) as usize);
+
+        for (local_pos, pos) in [(local.start, span.start), (local.end, span.end)] {
+            if !source.src.is_char_boundary(local_pos) {
+                return Err(SourceMapError::NotCharBoundary { pos });
+            }
+        }
+
+        Ok(ResolvedSpan {
+            file,
+            span: local,
+            snippet: &source.src()[local],
+        })
+    }
+
+    /// Converts a span inside of a file to a global span
+    pub fn global_span(
+        &self,
+        file: FileId,
+        local: Span<usize>,
+    ) -> Result<Span<u32>, SourceMapError> {
+        if local.start > local.end {
+            return Err(SourceMapError::Inverted);
+        }
+
+        let source = self.file(file);
+        if local.end > source.src.len() {
+            return Err(SourceMapError::OutOfBounds {
+                len: source.src.len(),
+            });
+        }
+
+        // The file's length fits in a `u32`, so these casts can't truncate
+        Ok(Span::from(
+            source.start + local.start as u32..source.start + local.end as u32,
+        ))
+    }
+}
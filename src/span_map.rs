@@ -0,0 +1,447 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{cmp::Ordering, fmt::Debug, iter::FusedIterator, mem};
+
+use crate::{Span, SpanIndex};
+
+/// A map from possibly overlapping [`Span`]s to values that supports efficient
+/// overlap queries.
+///
+/// This is an interval tree: entries are ordered by their start and then their
+/// end, and every subtree tracks the largest end it contains. The query
+/// iterators run in `O(log n + k)` and yield entries in that order.
+///
+/// Queries use the same half-open semantics as [`Span::contains`] and
+/// [`Span::overlaps_with`]. Inverted spans are never stored.
+/// ```rust
+/// # use copyspan::{Span, SpanMap};
+/// let mut map = SpanMap::new();
+/// map.insert(Span::from(0..10), "function");
+/// map.insert(Span::from(2..4), "name");
+/// map.insert(Span::from(6..9), "body");
+///
+/// let at_3: Vec<_> = map.containing(3).map(|(_, v)| *v).collect();
+/// assert_eq!(at_3, ["function", "name"]);
+///
+/// let in_body: Vec<_> = map.enclosed_by(Span::from(5..10)).map(|(_, v)| *v).collect();
+/// assert_eq!(in_body, ["body"]);
+///
+/// assert_eq!(map.overlapping(Span::from(4..6)).count(), 1);
+/// ```
+#[derive(Clone)]
+pub struct SpanMap<T: SpanIndex, V> {
+    root: Link<T, V>,
+    len: usize,
+}
+
+type Link<T, V> = Option<Box<Node<T, V>>>;
+
+#[derive(Clone)]
+struct Node<T: SpanIndex, V> {
+    span: Span<T>,
+    value: V,
+    /// The largest `end` of any span in this subtree
+    max_end: T,
+    height: u8,
+    left: Link<T, V>,
+    right: Link<T, V>,
+}
+
+impl<T: SpanIndex, V> SpanMap<T, V> {
+    /// Creates an empty map
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    /// The number of entries in this map
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    /// Inserts a value for a span. If the map already had a value for an
+    /// identical span, it is replaced and returned.
+    ///
+    /// Inverted spans are ignored, and their value is dropped.
+    /// ```rust
+    /// # use copyspan::{Span, SpanMap};
+    /// let mut map = SpanMap::new();
+    /// assert_eq!(map.insert(Span::from(2..2), 'a'), None);
+    /// assert_eq!(map.insert(Span::from(8..2), 'b'), None);
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(map.get(Span::from(8..2)), None);
+    /// ```
+    pub fn insert(&mut self, span: Span<T>, value: V) -> Option<V> {
+        // The tree prunes subtrees by their largest end, which assumes that
+        // no span ends before it starts
+        if span.start > span.end {
+            return None;
+        }
+
+        let old = insert(&mut self.root, span, value);
+
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    /// Removes the entry for an identical span and returns its value
+    pub fn remove(&mut self, span: Span<T>) -> Option<V> {
+        let node = remove(&mut self.root, span)?;
+        self.len -= 1;
+
+        Some(node.value)
+    }
+
+    /// Gets the value for an identical span
+    #[must_use]
+    pub fn get(&self, span: Span<T>) -> Option<&V> {
+        let mut node = self.root.as_deref();
+
+        while let Some(n) = node {
+            node = match cmp_spans(span, n.span) {
+                Ordering::Less => n.left.as_deref(),
+                Ordering::Greater => n.right.as_deref(),
+                Ordering::Equal => return Some(&n.value),
+            };
+        }
+
+        None
+    }
+
+    /// Gets a mutable reference to the value for an identical span
+    #[must_use]
+    pub fn get_mut(&mut self, span: Span<T>) -> Option<&mut V> {
+        let mut node = self.root.as_deref_mut();
+
+        while let Some(n) = node {
+            node = match cmp_spans(span, n.span) {
+                Ordering::Less => n.left.as_deref_mut(),
+                Ordering::Greater => n.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut n.value),
+            };
+        }
+
+        None
+    }
+
+    #[must_use]
+    pub fn contains_span(&self, span: Span<T>) -> bool {
+        self.get(span).is_some()
+    }
+
+    /// Iterates over every entry in the map
+    pub fn iter(&self) -> Search<'_, T, V> {
+        Search::new(self.root.as_deref(), Query::All)
+    }
+
+    /// Iterates over every entry whose span overlaps with `span` according to
+    /// [`Span::overlaps_with`]
+    pub fn overlapping(&self, span: Span<T>) -> Search<'_, T, V> {
+        Search::new(self.root.as_deref(), Query::Overlapping(span))
+    }
+
+    /// Iterates over every entry whose span contains `pos` according to
+    /// [`Span::contains`]. Empty spans never contain anything.
+    pub fn containing(&self, pos: T) -> Search<'_, T, V> {
+        Search::new(self.root.as_deref(), Query::Containing(pos))
+    }
+
+    /// Iterates over every entry whose span lies entirely inside of `span`.
+    /// This includes empty spans at either end of `span`.
+    pub fn enclosed_by(&self, span: Span<T>) -> Search<'_, T, V> {
+        Search::new(self.root.as_deref(), Query::EnclosedBy(span))
+    }
+}
+
+fn cmp_spans<T: SpanIndex>(a: Span<T>, b: Span<T>) -> Ordering {
+    (a.start, a.end).cmp(&(b.start, b.end))
+}
+
+impl<T: SpanIndex, V> Node<T, V> {
+    fn new(span: Span<T>, value: V) -> Self {
+        Self {
+            span,
+            value,
+            max_end: span.end,
+            height: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    fn update(&mut self) {
+        let left = self.left.as_deref();
+        let right = self.right.as_deref();
+
+        self.height = 1 + height(left).max(height(right));
+        self.max_end = [left, right]
+            .into_iter()
+            .flatten()
+            .fold(self.span.end, |acc, n| acc.max(n.max_end));
+    }
+}
+
+fn height<T: SpanIndex, V>(node: Option<&Node<T, V>>) -> u8 {
+    node.map_or(0, |n| n.height)
+}
+
+fn rotate_left<T: SpanIndex, V>(node: &mut Box<Node<T, V>>) {
+    let mut right = node.right.take().unwrap();
+    node.right = right.left.take();
+    node.update();
+
+    mem::swap(node, &mut right);
+    node.left = Some(right);
+    node.update();
+}
+
+fn rotate_right<T: SpanIndex, V>(node: &mut Box<Node<T, V>>) {
+    let mut left = node.left.take().unwrap();
+    node.left = left.right.take();
+    node.update();
+
+    mem::swap(node, &mut left);
+    node.right = Some(left);
+    node.update();
+}
+
+fn rebalance<T: SpanIndex, V>(link: &mut Link<T, V>) {
+    let Some(node) = link else {
+        return;
+    };
+
+    node.update();
+
+    let left = height(node.left.as_deref());
+    let right = height(node.right.as_deref());
+
+    if left > right + 1 {
+        let l = node.left.as_mut().unwrap();
+        if height(l.left.as_deref()) < height(l.right.as_deref()) {
+            rotate_left(l);
+        }
+
+        rotate_right(node);
+    } else if right > left + 1 {
+        let r = node.right.as_mut().unwrap();
+        if height(r.right.as_deref()) < height(r.left.as_deref()) {
+            rotate_right(r);
+        }
+
+        rotate_left(node);
+    }
+}
+
+fn insert<T: SpanIndex, V>(link: &mut Link<T, V>, span: Span<T>, value: V) -> Option<V> {
+    let Some(node) = link else {
+        *link = Some(Box::new(Node::new(span, value)));
+        return None;
+    };
+
+    let old = match cmp_spans(span, node.span) {
+        Ordering::Less => insert(&mut node.left, span, value),
+        Ordering::Greater => insert(&mut node.right, span, value),
+        Ordering::Equal => return Some(mem::replace(&mut node.value, value)),
+    };
+
+    rebalance(link);
+    old
+}
+
+fn remove<T: SpanIndex, V>(link: &mut Link<T, V>, span: Span<T>) -> Option<Box<Node<T, V>>> {
+    let node = link.as_mut()?;
+
+    let removed = match cmp_spans(span, node.span) {
+        Ordering::Less => remove(&mut node.left, span),
+        Ordering::Greater => remove(&mut node.right, span),
+        Ordering::Equal => {
+            let mut removed = link.take().unwrap();
+
+            *link = match (removed.left.take(), removed.right.take()) {
+                (None, None) => None,
+                (Some(child), None) | (None, Some(child)) => Some(child),
+                (Some(left), Some(right)) => {
+                    let mut right = Some(right);
+                    let mut successor = remove_min(&mut right);
+
+                    successor.left = Some(left);
+                    successor.right = right;
+                    Some(successor)
+                }
+            };
+
+            Some(removed)
+        }
+    };
+
+    rebalance(link);
+    removed
+}
+
+fn remove_min<T: SpanIndex, V>(link: &mut Link<T, V>) -> Box<Node<T, V>> {
+    let node = link.as_mut().unwrap();
+
+    if node.left.is_some() {
+        let min = remove_min(&mut node.left);
+        rebalance(link);
+        min
+    } else {
+        let mut min = link.take().unwrap();
+        *link = min.right.take();
+        min
+    }
+}
+
+impl<T: SpanIndex, V> Default for SpanMap<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SpanIndex, V: Debug> Debug for SpanMap<T, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T: SpanIndex, V> Extend<(Span<T>, V)> for SpanMap<T, V> {
+    fn extend<I: IntoIterator<Item = (Span<T>, V)>>(&mut self, iter: I) {
+        for (span, value) in iter {
+            self.insert(span, value);
+        }
+    }
+}
+
+impl<T: SpanIndex, V> FromIterator<(Span<T>, V)> for SpanMap<T, V> {
+    fn from_iter<I: IntoIterator<Item = (Span<T>, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, T: SpanIndex, V> IntoIterator for &'a SpanMap<T, V> {
+    type Item = (Span<T>, &'a V);
+
+    type IntoIter = Search<'a, T, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Query<T: SpanIndex> {
+    All,
+    Overlapping(Span<T>),
+    Containing(T),
+    EnclosedBy(Span<T>),
+}
+
+impl<T: SpanIndex> Query<T> {
+    /// Whether any span in a subtree with this `max_end` may match
+    fn subtree_may_match(self, max_end: T) -> bool {
+        match self {
+            Query::All => true,
+            Query::Overlapping(q) => max_end >= q.start,
+            Query::Containing(pos) => max_end > pos,
+            Query::EnclosedBy(q) => max_end >= q.start,
+        }
+    }
+
+    /// Whether any span starting before `start` may match
+    fn before_may_match(self, start: T) -> bool {
+        match self {
+            Query::EnclosedBy(q) => start >= q.start,
+            _ => true,
+        }
+    }
+
+    /// Whether any span starting after `start` may match
+    fn after_may_match(self, start: T) -> bool {
+        match self {
+            Query::All => true,
+            Query::Overlapping(q) => start <= q.start || start < q.end,
+            Query::Containing(pos) => start <= pos,
+            Query::EnclosedBy(q) => start <= q.end,
+        }
+    }
+
+    fn matches(self, span: Span<T>) -> bool {
+        match self {
+            Query::All => true,
+            Query::Overlapping(q) => span.overlaps_with(q),
+            Query::Containing(pos) => span.contains(pos),
+            Query::EnclosedBy(q) => q.start <= span.start && span.end <= q.end,
+        }
+    }
+}
+
+/// An iterator over the entries of a [`SpanMap`] that match a query
+#[derive(Clone)]
+pub struct Search<'a, T: SpanIndex, V> {
+    stack: Vec<&'a Node<T, V>>,
+    query: Query<T>,
+}
+
+impl<'a, T: SpanIndex, V> Search<'a, T, V> {
+    fn new(root: Option<&'a Node<T, V>>, query: Query<T>) -> Self {
+        let mut search = Self {
+            stack: Vec::new(),
+            query,
+        };
+
+        search.push_left_spine(root);
+        search
+    }
+
+    fn push_left_spine(&mut self, mut node: Option<&'a Node<T, V>>) {
+        while let Some(n) = node {
+            if !self.query.subtree_may_match(n.max_end) {
+                break;
+            }
+
+            self.stack.push(n);
+
+            node = if self.query.before_may_match(n.span.start) {
+                n.left.as_deref()
+            } else {
+                None
+            };
+        }
+    }
+}
+
+impl<'a, T: SpanIndex, V> Iterator for Search<'a, T, V> {
+    type Item = (Span<T>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.stack.pop()?;
+
+            if self.query.after_may_match(node.span.start) {
+                self.push_left_spine(node.right.as_deref());
+            }
+
+            if self.query.matches(node.span) {
+                return Some((node.span, &node.value));
+            }
+        }
+    }
+}
+
+impl<T: SpanIndex, V> FusedIterator for Search<'_, T, V> {}
@@ -0,0 +1,444 @@
+use alloc::vec::Vec;
+use core::{fmt::Debug, iter::FusedIterator, slice};
+
+use crate::{Span, SpanIndex};
+
+/// A set of positions stored as sorted, disjoint and coalesced [`Span`]s.
+///
+/// Empty and inverted spans are never stored, and spans that overlap or touch
+/// are merged together, so every set has exactly one representation.
+/// ```rust
+/// # use copyspan::{Span, SpanSet};
+/// let mut set = SpanSet::new();
+/// set.insert(Span::from(0..3));
+/// set.insert(Span::from(5..8));
+/// set.insert(Span::from(3..4));
+///
+/// assert_eq!(set.as_slice(), &[Span::from(0..4), Span::from(5..8)]);
+/// assert_eq!(set.covered_len(), 7);
+///
+/// set.remove(Span::from(1..6));
+/// assert_eq!(set.as_slice(), &[Span::from(0..1), Span::from(6..8)]);
+/// assert_eq!(set.gaps().collect::<Vec<_>>(), [Span::from(1..6)]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SpanSet<T: SpanIndex = usize> {
+    spans: Vec<Span<T>>,
+}
+
+impl<T: SpanIndex> SpanSet<T> {
+    /// Creates an empty set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { spans: Vec::new() }
+    }
+
+    /// The member spans of this set in ascending order
+    #[must_use]
+    pub fn as_slice(&self) -> &[Span<T>] {
+        &self.spans
+    }
+
+    /// Checks if this set contains no positions
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// The number of disjoint spans in this set
+    #[must_use]
+    pub fn span_count(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// The total number of positions covered by this set
+    ///
+    /// # Panics
+    /// May panic if the total doesn't fit in `T`, which can only happen for
+    /// signed types. See [`SpanSet::checked_covered_len`].
+    #[must_use]
+    pub fn covered_len(&self) -> T {
+        self.spans
+            .iter()
+            .fold(T::ZERO, |acc, s| acc + (s.end - s.start))
+    }
+
+    /// The total number of positions covered by this set, or `None` if it
+    /// doesn't fit in `T`
+    /// ```rust
+    /// # use copyspan::{Span, SpanSet};
+    /// let set = SpanSet::from(Span::from(-3..4i8));
+    /// assert_eq!(set.checked_covered_len(), Some(7));
+    ///
+    /// let set = SpanSet::from(Span::from(i64::MIN..i64::MAX));
+    /// assert_eq!(set.checked_covered_len(), None);
+    /// ```
+    #[must_use]
+    pub fn checked_covered_len(&self) -> Option<T> {
+        self.spans.iter().try_fold(T::ZERO, |acc, s| {
+            acc.checked_add(s.end.checked_sub(s.start)?)
+        })
+    }
+
+    /// The smallest span containing every member of this set
+    #[must_use]
+    pub fn bounds(&self) -> Option<Span<T>> {
+        let first = self.spans.first()?;
+        let last = self.spans.last()?;
+
+        Some(Span::from(first.start..last.end))
+    }
+
+    pub fn clear(&mut self) {
+        self.spans.clear();
+    }
+
+    /// Checks if a position is covered by this set
+    #[must_use]
+    pub fn contains(&self, pos: T) -> bool {
+        let idx = self.spans.partition_point(|s| s.end <= pos);
+
+        self.spans.get(idx).is_some_and(|s| s.start <= pos)
+    }
+
+    /// Checks if every position in `span` is covered by this set.
+    ///
+    /// Empty and inverted spans are always covered.
+    #[must_use]
+    pub fn covers(&self, span: Span<T>) -> bool {
+        if span.start >= span.end {
+            return true;
+        }
+
+        let idx = self.spans.partition_point(|s| s.end <= span.start);
+
+        self.spans
+            .get(idx)
+            .is_some_and(|s| s.start <= span.start && span.end <= s.end)
+    }
+
+    /// Checks if any position in `span` is covered by this set
+    #[must_use]
+    pub fn intersects(&self, span: Span<T>) -> bool {
+        if span.start >= span.end {
+            return false;
+        }
+
+        let idx = self.spans.partition_point(|s| s.end <= span.start);
+
+        self.spans.get(idx).is_some_and(|s| s.start < span.end)
+    }
+
+    /// Adds every position in `span` to this set. Empty and inverted spans are
+    /// ignored.
+    /// ```rust
+    /// # use copyspan::{Span, SpanSet};
+    /// let mut set = SpanSet::from(Span::from(3..4));
+    /// set.insert(Span::from(6..2));
+    ///
+    /// assert_eq!(set.as_slice(), &[Span::from(3..4)]);
+    ///
+    /// let set: SpanSet = [Span::from(6..2), Span::from(0..1)].into_iter().collect();
+    /// assert_eq!(set.as_slice(), &[Span::from(0..1)]);
+    /// ```
+    pub fn insert(&mut self, span: Span<T>) {
+        if span.start >= span.end {
+            return;
+        }
+
+        // Touching spans are merged as well as overlapping ones
+        let lo = self.spans.partition_point(|s| s.end < span.start);
+        let hi = self.spans.partition_point(|s| s.start <= span.end);
+
+        let mut merged = span;
+        if lo < hi {
+            merged.start = merged.start.min(self.spans[lo].start);
+            merged.end = merged.end.max(self.spans[hi - 1].end);
+        }
+
+        self.spans.splice(lo..hi, [merged]);
+    }
+
+    /// Removes every position in `span` from this set. Empty and inverted spans
+    /// remove nothing.
+    pub fn remove(&mut self, span: Span<T>) {
+        if span.start >= span.end {
+            return;
+        }
+
+        let lo = self.spans.partition_point(|s| s.end <= span.start);
+        let hi = self.spans.partition_point(|s| s.start < span.end);
+
+        if lo >= hi {
+            return;
+        }
+
+        let first = self.spans[lo];
+        let last = self.spans[hi - 1];
+
+        let left = (first.start < span.start).then(|| first.with_end(span.start));
+        let right = (span.end < last.end).then(|| last.with_start(span.end));
+
+        self.spans.splice(lo..hi, left.into_iter().chain(right));
+    }
+
+    /// The positions covered by `self`, `other` or both
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut spans = Vec::with_capacity(self.spans.len() + other.spans.len());
+        let mut a = self.spans.iter().peekable();
+        let mut b = other.spans.iter().peekable();
+
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.start <= y.start => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, Some(_)) => b.next(),
+                (None, None) => break,
+            };
+
+            push_coalesced(&mut spans, *next.unwrap());
+        }
+
+        Self { spans }
+    }
+
+    /// The positions covered by both `self` and `other`
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut spans = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.spans.get(i), other.spans.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+
+            if start < end {
+                spans.push(Span::from(start..end));
+            }
+
+            if a.end <= b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { spans }
+    }
+
+    /// The positions covered by `self` but not by `other`
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut spans = Vec::new();
+        let mut j = 0;
+
+        for &span in &self.spans {
+            let mut rest = span;
+
+            while let Some(b) = other.spans.get(j) {
+                if b.end <= rest.start {
+                    j += 1;
+                    continue;
+                }
+
+                if b.start >= rest.end {
+                    break;
+                }
+
+                if rest.start < b.start {
+                    spans.push(rest.with_end(b.start));
+                }
+
+                if b.end >= rest.end {
+                    rest = rest.span_after();
+                    break;
+                }
+
+                rest.start = b.end;
+                j += 1;
+            }
+
+            if !rest.is_empty() {
+                spans.push(rest);
+            }
+        }
+
+        Self { spans }
+    }
+
+    /// The positions covered by exactly one of `self` and `other`
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.difference(other).union(&other.difference(self))
+    }
+
+    /// The positions inside of `bounds` that are not covered by this set
+    /// ```rust
+    /// # use copyspan::{Span, SpanSet};
+    /// let set = SpanSet::from_iter([Span::from(2..4), Span::from(6..12)]);
+    ///
+    /// assert_eq!(
+    ///     set.complement(Span::from(0..8)).as_slice(),
+    ///     &[Span::from(0..2), Span::from(4..6)],
+    /// );
+    /// ```
+    #[must_use]
+    pub fn complement(&self, bounds: Span<T>) -> Self {
+        let mut spans = Vec::new();
+        let mut cursor = bounds.start;
+
+        for span in &self.spans {
+            if span.start >= bounds.end {
+                break;
+            }
+
+            if cursor < span.start {
+                spans.push(Span::from(cursor..span.start));
+            }
+
+            cursor = cursor.max(span.end);
+        }
+
+        if cursor < bounds.end {
+            spans.push(Span::from(cursor..bounds.end));
+        }
+
+        Self { spans }
+    }
+
+    /// Iterates over the member spans of this set in ascending order
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.spans.iter(),
+        }
+    }
+
+    /// Iterates over the uncovered spans between consecutive members of this
+    /// set
+    pub fn gaps(&self) -> Gaps<'_, T> {
+        Gaps {
+            inner: self.spans.windows(2),
+        }
+    }
+}
+
+/// Appends a span that starts at or after every span in `spans`, merging it
+/// with the last span if they overlap or touch. Empty and inverted spans are
+/// skipped.
+fn push_coalesced<T: SpanIndex>(spans: &mut Vec<Span<T>>, span: Span<T>) {
+    if span.start >= span.end {
+        return;
+    }
+
+    match spans.last_mut() {
+        Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+        _ => spans.push(span),
+    }
+}
+
+impl<T: SpanIndex> Default for SpanSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SpanIndex> Debug for SpanSet<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(&self.spans).finish()
+    }
+}
+
+impl<T: SpanIndex> From<Span<T>> for SpanSet<T> {
+    fn from(value: Span<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(value);
+        set
+    }
+}
+
+impl<T: SpanIndex> Extend<Span<T>> for SpanSet<T> {
+    fn extend<I: IntoIterator<Item = Span<T>>>(&mut self, iter: I) {
+        let mut spans: Vec<_> = self.spans.drain(..).chain(iter).collect();
+        spans.sort_unstable_by_key(|s| s.start);
+
+        for span in spans {
+            push_coalesced(&mut self.spans, span);
+        }
+    }
+}
+
+impl<T: SpanIndex> FromIterator<Span<T>> for SpanSet<T> {
+    fn from_iter<I: IntoIterator<Item = Span<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, T: SpanIndex> IntoIterator for &'a SpanSet<T> {
+    type Item = Span<T>;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the member spans of a [`SpanSet`]
+#[derive(Clone)]
+pub struct Iter<'a, T: SpanIndex> {
+    inner: slice::Iter<'a, Span<T>>,
+}
+
+impl<T: SpanIndex> Iterator for Iter<'_, T> {
+    type Item = Span<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().copied()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: SpanIndex> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().copied()
+    }
+}
+
+impl<T: SpanIndex> ExactSizeIterator for Iter<'_, T> {}
+impl<T: SpanIndex> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over the gaps between the member spans of a [`SpanSet`]
+#[derive(Clone)]
+pub struct Gaps<'a, T: SpanIndex> {
+    inner: slice::Windows<'a, Span<T>>,
+}
+
+impl<T: SpanIndex> Iterator for Gaps<'_, T> {
+    type Item = Span<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|w| Span::from(w[0].end..w[1].start))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: SpanIndex> DoubleEndedIterator for Gaps<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|w| Span::from(w[0].end..w[1].start))
+    }
+}
+
+impl<T: SpanIndex> ExactSizeIterator for Gaps<'_, T> {}
+impl<T: SpanIndex> FusedIterator for Gaps<'_, T> {}
@@ -0,0 +1,201 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::{
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
+
+use crate::{Span, SpanIndex};
+
+/// A value along with the [`Span`] that it came from.
+///
+/// Comparisons and hashing take both the value and the span into account. Use
+/// [`Spanned::ignore_span`] to only compare the values.
+/// ```rust
+/// # use copyspan::{Span, Spanned};
+/// let ident = Spanned::new("foo", Span::from(4..7));
+/// let len = ident.map(str::len);
+///
+/// assert_eq!(*len, 3);
+/// assert_eq!(len.span, Span::from(4..7));
+///
+/// let other = Spanned::new("foo", Span::from(20..23));
+/// assert_ne!(ident, other);
+/// assert_eq!(ident.ignore_span(), other.ignore_span());
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "alloc", derive(Debug))]
+pub struct Spanned<T, S: SpanIndex = usize> {
+    pub node: T,
+    pub span: Span<S>,
+}
+
+impl<T, S: SpanIndex> Spanned<T, S> {
+    #[must_use]
+    pub const fn new(node: T, span: Span<S>) -> Self {
+        Self { node, span }
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.node
+    }
+
+    /// Transforms the value without changing the span
+    #[must_use]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U, S> {
+        Spanned {
+            node: f(self.node),
+            span: self.span,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_ref(&self) -> Spanned<&T, S> {
+        Spanned {
+            node: &self.node,
+            span: self.span,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_mut(&mut self) -> Spanned<&mut T, S> {
+        Spanned {
+            node: &mut self.node,
+            span: self.span,
+        }
+    }
+
+    /// Wraps this value so that comparisons and hashing ignore the span
+    #[must_use]
+    pub const fn ignore_span(self) -> IgnoreSpan<T, S> {
+        IgnoreSpan(self)
+    }
+}
+
+impl<T, S: SpanIndex> Deref for Spanned<T, S> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+impl<T, S: SpanIndex> DerefMut for Spanned<T, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.node
+    }
+}
+
+impl<T, S: SpanIndex> From<(T, Span<S>)> for Spanned<T, S> {
+    fn from((node, span): (T, Span<S>)) -> Self {
+        Self { node, span }
+    }
+}
+
+impl<T, S: SpanIndex> From<Spanned<T, S>> for (T, Span<S>) {
+    fn from(value: Spanned<T, S>) -> Self {
+        (value.node, value.span)
+    }
+}
+
+/// A [`Spanned`] value whose comparisons and hashing ignore its span
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "alloc", derive(Debug))]
+#[repr(transparent)]
+pub struct IgnoreSpan<T, S: SpanIndex = usize>(pub Spanned<T, S>);
+
+impl<T, S: SpanIndex> Deref for IgnoreSpan<T, S> {
+    type Target = Spanned<T, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, S: SpanIndex> DerefMut for IgnoreSpan<T, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: PartialEq, S: SpanIndex> PartialEq for IgnoreSpan<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.node == other.0.node
+    }
+}
+
+impl<T: Eq, S: SpanIndex> Eq for IgnoreSpan<T, S> {}
+
+impl<T: Hash, S: SpanIndex> Hash for IgnoreSpan<T, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.node.hash(state);
+    }
+}
+
+/// Something that covers a region of source text.
+///
+/// Values that cover nothing, such as `None` or an empty slice, return `None`.
+/// ```rust
+/// # use copyspan::{HasSpan, Span, Spanned};
+/// let args = [
+///     Spanned::new('a', Span::from(4..5)),
+///     Spanned::new('b', Span::from(7..8)),
+/// ];
+///
+/// assert_eq!(args.span(), Some(Span::from(4..8)));
+/// assert_eq!(args[..0].span(), None);
+/// ```
+pub trait HasSpan<S: SpanIndex = usize> {
+    fn span(&self) -> Option<Span<S>>;
+}
+
+impl<S: SpanIndex> HasSpan<S> for Span<S> {
+    fn span(&self) -> Option<Span<S>> {
+        Some(*self)
+    }
+}
+
+impl<T, S: SpanIndex> HasSpan<S> for Spanned<T, S> {
+    fn span(&self) -> Option<Span<S>> {
+        Some(self.span)
+    }
+}
+
+impl<T, S: SpanIndex> HasSpan<S> for IgnoreSpan<T, S> {
+    fn span(&self) -> Option<Span<S>> {
+        Some(self.0.span)
+    }
+}
+
+impl<T, S: SpanIndex> HasSpan<S> for (T, Span<S>) {
+    fn span(&self) -> Option<Span<S>> {
+        Some(self.1)
+    }
+}
+
+impl<S: SpanIndex, T: HasSpan<S> + ?Sized> HasSpan<S> for &T {
+    fn span(&self) -> Option<Span<S>> {
+        (**self).span()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: SpanIndex, T: HasSpan<S> + ?Sized> HasSpan<S> for Box<T> {
+    fn span(&self) -> Option<Span<S>> {
+        (**self).span()
+    }
+}
+
+impl<S: SpanIndex, T: HasSpan<S>> HasSpan<S> for Option<T> {
+    fn span(&self) -> Option<Span<S>> {
+        self.as_ref()?.span()
+    }
+}
+
+/// The hull of every element that has a span
+impl<S: SpanIndex, T: HasSpan<S>> HasSpan<S> for [T] {
+    fn span(&self) -> Option<Span<S>> {
+        self.iter().filter_map(HasSpan::span).reduce(Span::hull)
+    }
+}
@@ -0,0 +1,196 @@
+use core::ops::{Add, Sub};
+
+use crate::{Span, SpanError, SpanIndex};
+
+macro_rules! unit_index {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(transparent)]
+        pub struct $name<T: SpanIndex = usize>(pub T);
+
+        impl<T: SpanIndex> SpanIndex for $name<T> {
+            const ZERO: Self = Self(T::ZERO);
+            const ONE: Self = Self(T::ONE);
+            const MIN: Self = Self(T::MIN);
+            const MAX: Self = Self(T::MAX);
+
+            fn to_i128(self) -> i128 {
+                self.0.to_i128()
+            }
+
+            fn from_i128(value: i128) -> Option<Self> {
+                T::from_i128(value).map(Self)
+            }
+
+            fn to_usize(self) -> Option<usize> {
+                self.0.to_usize()
+            }
+
+            fn from_usize(value: usize) -> Option<Self> {
+                T::from_usize(value).map(Self)
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.0.checked_add(rhs.0).map(Self)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.0.checked_sub(rhs.0).map(Self)
+            }
+
+            fn saturating_add(self, rhs: Self) -> Self {
+                Self(self.0.saturating_add(rhs.0))
+            }
+
+            fn saturating_sub(self, rhs: Self) -> Self {
+                Self(self.0.saturating_sub(rhs.0))
+            }
+
+            fn wrapping_add(self, rhs: Self) -> Self {
+                Self(self.0.wrapping_add(rhs.0))
+            }
+
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                Self(self.0.wrapping_sub(rhs.0))
+            }
+        }
+
+        impl<T: SpanIndex> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl<T: SpanIndex> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+    };
+}
+
+unit_index!(
+    /// An index that counts UTF-16 code units, like the positions used by the
+    /// language server protocol.
+    ///
+    /// Spans of `Utf16` can't index a `str` directly. Convert them to bytes
+    /// with their `to_bytes` method first.
+    /// ```rust
+    /// # use copyspan::{Span, Utf16};
+    /// let text = "a 🦀 b";
+    ///
+    /// // The crab is two UTF-16 code units and four bytes long
+    /// let crab = Span::from(Utf16(2)..Utf16(4));
+    ///
+    /// let bytes = crab.to_bytes(text).unwrap();
+    /// assert_eq!(&text[bytes], "🦀");
+    /// assert_eq!(bytes.to_utf16(text), Ok(crab));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use copyspan::{Span, Utf16};
+    /// let crab = &"a 🦀 b"[Span::from(Utf16(2)..Utf16(4))];
+    /// ```
+    Utf16
+);
+
+unit_index!(
+    /// An index that counts `char`s.
+    ///
+    /// Spans of `Chars` can't index a `str` directly. Convert them to bytes
+    /// with their `to_bytes` method first.
+    /// ```rust
+    /// # use copyspan::{Chars, Span};
+    /// let text = "héllo";
+    ///
+    /// let span = Span::from(Chars(1)..Chars(3));
+    /// assert_eq!(&text[span.to_bytes(text).unwrap()], "él");
+    /// ```
+    Chars
+);
+
+/// Converts a byte offset into an offset in some other unit, where each `char`
+/// is `width(c)` units long
+fn from_bytes(text: &str, offset: usize, width: fn(char) -> usize) -> Result<usize, SpanError> {
+    if offset > text.len() {
+        return Err(SpanError::OutOfBounds { len: text.len() });
+    }
+
+    if !text.is_char_boundary(offset) {
+        return Err(SpanError::NotCharBoundary { at: offset });
+    }
+
+    Ok(text[..offset].chars().map(width).sum())
+}
+
+/// The inverse of [`from_bytes`]. Errors refer to positions in the other
+/// unit.
+fn to_bytes(text: &str, offset: usize, width: fn(char) -> usize) -> Result<usize, SpanError> {
+    let mut units = 0;
+
+    for (i, c) in text.char_indices() {
+        if units == offset {
+            return Ok(i);
+        }
+
+        units += width(c);
+
+        if units > offset {
+            return Err(SpanError::NotCharBoundary { at: offset });
+        }
+    }
+
+    if units == offset {
+        Ok(text.len())
+    } else {
+        Err(SpanError::OutOfBounds { len: units })
+    }
+}
+
+/// Converts each end of a span with `f`
+fn convert<T: SpanIndex, U: SpanIndex>(
+    span: Span<T>,
+    f: impl Fn(usize) -> Result<usize, SpanError>,
+) -> Result<Span<U>, SpanError> {
+    let [start, end] = [span.start, span.end].map(|pos| {
+        let pos = f(pos.to_usize().ok_or(SpanError::Overflow)?)?;
+        U::from_usize(pos).ok_or(SpanError::Overflow)
+    });
+
+    Span::new(start?, end?)
+}
+
+impl Span<usize> {
+    /// Converts a span of bytes in `text` to a span of UTF-16 code units
+    pub fn to_utf16(self, text: &str) -> Result<Span<Utf16>, SpanError> {
+        convert(self, |pos| from_bytes(text, pos, char::len_utf16))
+    }
+
+    /// Converts a span of bytes in `text` to a span of `char`s
+    pub fn to_chars(self, text: &str) -> Result<Span<Chars>, SpanError> {
+        convert(self, |pos| from_bytes(text, pos, |_| 1))
+    }
+}
+
+impl Span<Utf16> {
+    /// Converts a span of UTF-16 code units in `text` to a span of bytes.
+    ///
+    /// Positions in the returned errors are in UTF-16 code units.
+    pub fn to_bytes(self, text: &str) -> Result<Span<usize>, SpanError> {
+        convert(self, |pos| to_bytes(text, pos, char::len_utf16))
+    }
+}
+
+impl Span<Chars> {
+    /// Converts a span of `char`s in `text` to a span of bytes.
+    ///
+    /// Positions in the returned errors are in `char`s.
+    pub fn to_bytes(self, text: &str) -> Result<Span<usize>, SpanError> {
+        convert(self, |pos| to_bytes(text, pos, |_| 1))
+    }
+}